@@ -9,7 +9,7 @@ The example demonstrates querying blocks of transactions - we can query blocks o
 after calling function:  Iroha.blocks_query
 """
 
-from iroha import Iroha, IrohaGrpc
+from iroha import Iroha, IrohaGrpc, AccountId
 from iroha import IrohaCrypto
 import os
 import sys
@@ -34,7 +34,7 @@ net = IrohaGrpc(f'{IROHA_HOST_ADDR}:{IROHA_PORT}')
 def send_create_account_transaction():
     rand_name = uuid.uuid4().hex
     rand_key = IrohaCrypto.private_key()
-    domain = ADMIN_ACCOUNT_ID.split('@')[1]
+    domain = AccountId.parse(ADMIN_ACCOUNT_ID).domain
     tx = iroha.transaction([
         iroha.command('CreateAccount',
                       account_name=rand_name,
@@ -13,7 +13,8 @@ import binascii
 import time
 from grpc import RpcError, StatusCode
 import inspect  # inspect.stack(0)
-from iroha import Iroha, IrohaGrpc, IrohaCrypto
+from iroha import Iroha, IrohaGrpc, IrohaCrypto, AccountId
+from iroha.mst import MstWorkflow
 from functools import wraps
 from iroha.primitive_pb2 import can_set_my_account_detail, can_set_my_quorum
 from utilities.errorCodes2Hr import get_proper_functions_for_commands
@@ -119,8 +120,8 @@ def create_user_accounts():
 
 @trace
 def create_account(account_id, public_key):
-    account_short_id, domain = account_id.split("@")
-    return iroha.command('CreateAccount', account_name=account_short_id, domain_id=domain,
+    account = AccountId.parse(account_id)
+    return iroha.command('CreateAccount', account_name=account.name, domain_id=account.domain,
                          public_key=public_key)
 
 
@@ -205,19 +206,11 @@ def mint_asset(asset_id, amount):
 
 @trace
 def sign_pending_transactions(account_id, private_key):
-    global net
-    query = IrohaCrypto.sign_query(Iroha(account_id).query('GetPendingTransactions'), private_key)
-    pending_transactions = net.send_query(query)
-    print(len(pending_transactions.transactions_response.transactions))
-    for tx in pending_transactions.transactions_response.transactions:
-        print('creator: {}'.format(tx.payload.reduced_payload.creator_account_id))
-        if tx.payload.reduced_payload.creator_account_id == account_id:
-            # we need do this temporarily, otherwise accept will not reach MST engine
-            print(f'tx: {tx}')
-            del tx.signatures[:]
-            print(f'tx: {tx}')
-            IrohaCrypto.sign_transaction(tx, private_key)
-            send_transaction_and_print_status(tx)
+    workflow = MstWorkflow(Iroha(account_id), net, private_key)
+    for pending in workflow.awaiting():
+        print(f'pending: {pending}, missing keys: {pending.missing_keys}')
+        workflow.co_sign(pending)
+        print(f'states: {workflow.wait(pending, timeout=30)}')
 
 
 @trace
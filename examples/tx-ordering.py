@@ -9,7 +9,7 @@ import sys
 import binascii
 import datetime
 import inspect  # inspect.stack(0)
-from iroha import IrohaCrypto, Iroha, IrohaGrpc, queries_pb2
+from iroha import IrohaCrypto, Iroha, IrohaGrpc, queries_pb2, AccountId, AssetId
 from functools import wraps
 from time import sleep
 from grpc import RpcError, StatusCode
@@ -83,9 +83,9 @@ def get_commands_from_tx(transaction):
 
 @trace
 def create_account(user_account: str, user_public_key: str):
-    account, domain = user_account.split('@')
+    account_id = AccountId.parse(user_account)
     tx = iroha.transaction([
-        iroha.command('CreateAccount', account_name=account, domain_id=domain,
+        iroha.command('CreateAccount', account_name=account_id.name, domain_id=account_id.domain,
                       public_key=user_public_key)
     ])
     IrohaCrypto.sign_transaction(tx, ADMIN_PRIVATE_KEY)
@@ -94,9 +94,9 @@ def create_account(user_account: str, user_public_key: str):
 
 @trace
 def create_asset(asset_id: str):
-    asset, domain = asset_id.split('#')
+    asset = AssetId.parse(asset_id)
     tx = iroha.transaction([
-        iroha.command('CreateAsset', asset_name=asset, domain_id=domain, precision=2)
+        iroha.command('CreateAsset', asset_name=asset.name, domain_id=asset.domain, precision=2)
     ])
     IrohaCrypto.sign_transaction(tx, ADMIN_PRIVATE_KEY)
     send_transaction_and_print_status(tx)
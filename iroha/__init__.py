@@ -5,4 +5,8 @@ if sys.version_info[0] < 3:
     	'Python 3 or a more recent version is required. Python 2 is not supported.')
 
 from .iroha import *
+from .errors import *
+from .amount import Amount
+from .ids import AccountId, AssetId, DomainId, RoleId
+from .retry import RetryPolicy
 name = 'iroha'
@@ -0,0 +1,210 @@
+#!/usr/bin/env python3
+#
+# Copyright Soramitsu Co., Ltd. All Rights Reserved.
+# SPDX-License-Identifier: Apache-2.0
+#
+
+import asyncio
+import time
+
+import grpc
+from google.protobuf import empty_pb2
+
+from . import endpoint_pb2
+from . import endpoint_pb2_grpc
+from .iroha import IrohaCrypto, IrohaGrpc
+
+
+class IrohaGrpcAsync(object):
+    """
+    Possible implementation of asyncio gRPC transport to Iroha.
+    Mirrors IrohaGrpc, but all the calls are coroutines and streams are async iterators.
+    Requires grpcio with grpc.aio support (1.32 or newer)
+    """
+
+    def __init__(self, address=None, timeout=None, secure=False, root_certificates=None, private_key=None, certificate_chain=None, *, max_message_length=None):
+        """
+        Create Iroha asyncio gRPC client
+        :param address: Iroha Torii address with port, example "127.0.0.1:50051"
+        :param timeout: timeout for network I/O operations in seconds
+        :param secure: enable grpc ssl channel
+        :param max_message_length: it is max message length in bytes for grpc
+        :param root_certificates The PEM-encoded root certificates as a byte string,
+        or None to retrieve them from a default location chosen by gRPC
+        runtime. https://grpc.io/docs/guides/auth/
+        :param private_key The PEM-encoded private key as a byte string, or None if no
+        private key should be used.
+        :param certificate_chain The PEM-encoded certificate chain as a byte string
+        to use or None if no certificate chain should be used.
+        """
+        self._address = address if address else '127.0.0.1:50051'
+
+        channel_kwargs = IrohaGrpc._channel_kwargs(max_message_length)
+        if secure:
+            self._channel = grpc.aio.secure_channel(self._address, grpc.ssl_channel_credentials(
+                root_certificates, private_key, certificate_chain), **channel_kwargs)
+        else:
+            self._channel = grpc.aio.insecure_channel(self._address, **channel_kwargs)
+
+        self._timeout = timeout
+        self._command_service_stub = endpoint_pb2_grpc.CommandService_v1Stub(
+            self._channel)
+        self._query_service_stub = endpoint_pb2_grpc.QueryService_v1Stub(
+            self._channel)
+
+    async def __aenter__(self):
+        return self
+
+    async def __aexit__(self, exc_type, exc_value, traceback):
+        await self.close()
+
+    async def close(self):
+        """
+        Close the underlying channel and cancel all the active calls
+        :return: None
+        """
+        await self._channel.close()
+
+    async def send_tx(self, transaction, timeout=None):
+        """
+        Send a transaction to Iroha
+        :param transaction: protobuf Transaction
+        :param timeout: timeout for network I/O operations in seconds
+        :return: None
+        :raise: grpc.RpcError with .code() available in case of any error
+        """
+        if not timeout:
+            timeout = self._timeout
+        await self._command_service_stub.Torii(transaction, timeout=timeout)
+
+    async def send_txs(self, transactions, timeout=None):
+        """
+        Send a series of transactions to Iroha at once.
+        Useful for submitting batches of transactions.
+        :param transactions: list of protobuf transactions to be sent
+        :param timeout: timeout for network I/O operations in seconds
+        :return: None
+        :raise: grpc.RpcError with .code() available in case of any error
+        """
+        if not timeout:
+            timeout = self._timeout
+        tx_list = endpoint_pb2.TxList()
+        tx_list.transactions.extend(transactions)
+        await self._command_service_stub.ListTorii(tx_list, timeout=timeout)
+
+    async def send_query(self, query, timeout=None, unwrap=False):
+        """
+        Send a query to Iroha
+        :param query: protobuf Query
+        :param timeout: timeout for network I/O operations in seconds
+        :param unwrap: return the inner response message instead of QueryResponse
+        and raise QueryError subclass if Iroha answered with ErrorResponse
+        :return: a protobuf response to the query
+        :raise: grpc.RpcError with .code() available in case of any error,
+        QueryError subclass on ErrorResponse when unwrap is requested
+        """
+        if not timeout:
+            timeout = self._timeout
+        response = await self._query_service_stub.Find(query, timeout=timeout)
+        if unwrap:
+            return IrohaGrpc.unwrap_query_response(response)
+        return response
+
+    async def send_blocks_stream_query(self, query, timeout=None):
+        """
+        Send a query for blocks stream to Iroha
+        :param query: protobuf BlocksQuery
+        :param timeout: timeout for network I/O operations in seconds
+        :return: an async iterable over a stream of blocks
+        :raise: grpc.RpcError with .code() available in case of any error
+        """
+        if not timeout:
+            timeout = self._timeout
+        response = self._query_service_stub.FetchCommits(
+            query, timeout=timeout)
+        async for block in response:
+            yield block
+
+    async def healthcheck(self, timeout=None):
+        """
+        Request health information of the connected peer
+        :param timeout: timeout for network I/O operations in seconds
+        :return: a protobuf HealthcheckData
+        :raise: grpc.RpcError with .code() available in case of any error
+        """
+        if not timeout:
+            timeout = self._timeout
+        response = await self._query_service_stub.Healthcheck(
+            empty_pb2.Empty(), timeout=timeout)
+        return response
+
+    async def wait_until_ready(self, min_height=None, timeout=None, poll_interval=1.0):
+        """
+        Wait until the peer is healthy, not syncing and reached the given height.
+        Connection errors are treated as "not ready yet" while the peer is starting up.
+        :param min_height: optional block height the peer has to reach
+        :param timeout: overall time limit in seconds, None means wait forever
+        :param poll_interval: delay between healthcheck requests in seconds
+        :return: the last protobuf HealthcheckData received
+        :raise: TimeoutError if the peer has not become ready in time,
+        grpc.RpcError with .code() available in case of any non-transient error
+        """
+        deadline = time.monotonic() + timeout if timeout is not None else None
+        while True:
+            try:
+                data = await self.healthcheck()
+                if IrohaGrpc.is_ready(data, min_height):
+                    return data
+            except grpc.RpcError as rpc_error:
+                if rpc_error.code() not in (grpc.StatusCode.UNAVAILABLE,
+                                            grpc.StatusCode.DEADLINE_EXCEEDED):
+                    raise
+            if deadline is not None and time.monotonic() + poll_interval > deadline:
+                raise TimeoutError(
+                    'Peer {} is not ready after {} seconds'.format(self._address, timeout))
+            await asyncio.sleep(poll_interval)
+
+    async def tx_status(self, transaction, timeout=None):
+        """
+        Request a status of a transaction
+        :param transaction: the transaction, which status is about to be known
+        :param timeout: timeout for network I/O operations in seconds
+        :return: a tuple with the symbolic status description,
+        integral status code, and error code (will be 0 if no error occurred)
+        :raise: grpc.RpcError with .code() available in case of any error
+        """
+        if not timeout:
+            timeout = self._timeout
+        request = IrohaGrpc._tx_status_request(IrohaCrypto.hash(transaction))
+        response = await self._command_service_stub.Status(request, timeout=timeout)
+        return IrohaGrpc._parse_tx_status(response)
+
+    async def tx_status_stream(self, transaction, timeout=None):
+        """
+        Async generator of transaction statuses from status stream
+        :param transaction: the transaction, which status is about to be known
+        :param timeout: timeout for network I/O operations in seconds
+        :return: an async iterable over a series of tuples with symbolic status description,
+        integral status code, and error code (will be 0 if no error occurred)
+        :raise: grpc.RpcError with .code() available in case of any error
+        """
+        tx_hash = IrohaCrypto.hash(transaction)
+        async for status in self.tx_hash_status_stream(tx_hash, timeout):
+            yield status
+
+    async def tx_hash_status_stream(self, transaction_hash: "str or bytes", timeout=None):
+        """
+        Async generator of transaction statuses from status stream
+        :param transaction_hash: the hash of transaction, which status is about to be known
+        :param timeout: timeout for network I/O operations in seconds
+        :return: an async iterable over a series of tuples with symbolic status description,
+        integral status code, and error code (will be 0 if no error occurred)
+        :raise: grpc.RpcError with .code() available in case of any error
+        """
+        if not timeout:
+            timeout = self._timeout
+        request = IrohaGrpc._tx_status_request(transaction_hash)
+        response = self._command_service_stub.StatusStream(
+            request, timeout=timeout)
+        async for status in response:
+            yield IrohaGrpc._parse_tx_status(status)
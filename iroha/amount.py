@@ -0,0 +1,169 @@
+#!/usr/bin/env python3
+#
+# Copyright Soramitsu Co., Ltd. All Rights Reserved.
+# SPDX-License-Identifier: Apache-2.0
+#
+
+import decimal
+import re
+
+from .errors import ValidationError
+
+# Iroha keeps asset quantities as 256-bit unsigned integers scaled by 10^precision
+MAX_UNITS = 2 ** 256 - 1
+MAX_PRECISION = 255
+AMOUNT_PATTERN = re.compile(r'^[0-9]+(\.[0-9]+)?$')
+# enough digits to represent any amount exactly
+_CONTEXT = decimal.Context(prec=400)
+
+
+class Amount(object):
+    """
+    Non-negative asset quantity with a fixed number of digits after the decimal point.
+    Values are never rounded: a value with more digits than the precision is rejected.
+    str() gives the canonical form with exactly `precision` digits after the point.
+    """
+
+    __slots__ = ('_units', '_precision')
+
+    def __init__(self, value, precision=None):
+        """
+        :param value: str, int, Decimal or Amount, floats are rejected to avoid binary rounding
+        :param precision: number of digits after the point, by default the number of digits in the value
+        :raise: ValidationError if the value is malformed, negative, exceeds the precision
+        or does not fit into 256 bits
+        """
+        if isinstance(value, Amount):
+            number = value.value
+        elif isinstance(value, str):
+            if not AMOUNT_PATTERN.match(value):
+                raise ValidationError('amount', '{!r} is not a valid amount'.format(value))
+            number = decimal.Decimal(value)
+            if precision is None:
+                precision = len(value.partition('.')[2])
+        elif isinstance(value, decimal.Decimal):
+            if not value.is_finite():
+                raise ValidationError('amount', '{} is not a valid amount'.format(value))
+            number = value
+        elif isinstance(value, int) and not isinstance(value, bool):
+            number = decimal.Decimal(value)
+        else:
+            raise ValidationError('amount', 'str, int, Decimal or Amount expected, got {}'.format(
+                type(value).__name__))
+        if precision is None:
+            precision = value.precision if isinstance(value, Amount) else max(0, -number.as_tuple().exponent)
+        if isinstance(precision, bool) or not isinstance(precision, int) or not 0 <= precision <= MAX_PRECISION:
+            raise ValidationError('precision', '{!r} is out of range [0, {}]'.format(precision, MAX_PRECISION))
+        if number < 0:
+            raise ValidationError('amount', '{} is negative'.format(number))
+        scaled = number.scaleb(precision, _CONTEXT)
+        if scaled != scaled.to_integral_value():
+            raise ValidationError('amount', '{} has more than {} digits after the point'.format(number, precision))
+        units = int(scaled)
+        if units > MAX_UNITS:
+            raise ValidationError('amount', '{} does not fit into 256 bits with precision {}'.format(
+                number, precision))
+        object.__setattr__(self, '_units', units)
+        object.__setattr__(self, '_precision', precision)
+
+    @classmethod
+    def from_units(cls, units, precision):
+        """
+        :param units: integer quantity in the smallest units, e.g. cents for precision 2
+        :param precision: number of digits after the point
+        :return: Amount
+        """
+        if isinstance(units, bool) or not isinstance(units, int):
+            raise ValidationError('amount', 'integer units expected, got {}'.format(type(units).__name__))
+        return cls(decimal.Decimal(units).scaleb(-precision, _CONTEXT), precision)
+
+    @property
+    def units(self):
+        """Quantity in the smallest units as Iroha stores it"""
+        return self._units
+
+    @property
+    def precision(self):
+        return self._precision
+
+    @property
+    def value(self):
+        """Quantity as Decimal"""
+        return decimal.Decimal(self._units).scaleb(-self._precision, _CONTEXT)
+
+    def with_precision(self, precision):
+        """
+        :param precision: new number of digits after the point
+        :return: Amount with the same value and another precision
+        :raise: ValidationError if the value has more significant digits than the precision allows
+        """
+        return Amount(self.value, precision)
+
+    def __setattr__(self, name, value):
+        raise AttributeError('Amount is immutable')
+
+    def __str__(self):
+        digits = str(self._units).rjust(self._precision + 1, '0')
+        if not self._precision:
+            return digits
+        return '{}.{}'.format(digits[:-self._precision], digits[-self._precision:])
+
+    def __repr__(self):
+        return "Amount('{}')".format(self)
+
+    def _aligned(self, other):
+        if not isinstance(other, Amount):
+            other = Amount(other)
+        precision = max(self._precision, other._precision)
+        return (self._units * 10 ** (precision - self._precision),
+                other._units * 10 ** (precision - other._precision),
+                precision)
+
+    def __add__(self, other):
+        units, other_units, precision = self._aligned(other)
+        return Amount.from_units(units + other_units, precision)
+
+    def __sub__(self, other):
+        units, other_units, precision = self._aligned(other)
+        return Amount.from_units(units - other_units, precision)
+
+    def __eq__(self, other):
+        if not isinstance(other, (Amount, int, decimal.Decimal)) or isinstance(other, bool):
+            return NotImplemented
+        return self.value == (other.value if isinstance(other, Amount) else other)
+
+    def __ne__(self, other):
+        result = self.__eq__(other)
+        return result if result is NotImplemented else not result
+
+    def __lt__(self, other):
+        units, other_units, _ = self._aligned(other)
+        return units < other_units
+
+    def __le__(self, other):
+        units, other_units, _ = self._aligned(other)
+        return units <= other_units
+
+    def __gt__(self, other):
+        units, other_units, _ = self._aligned(other)
+        return units > other_units
+
+    def __ge__(self, other):
+        units, other_units, _ = self._aligned(other)
+        return units >= other_units
+
+    def __hash__(self):
+        return hash(self.value)
+
+    def __bool__(self):
+        return self._units != 0
+
+
+def balance(account_asset, precision=None):
+    """
+    Parse the balance of a protobuf AccountAsset
+    :param account_asset: protobuf AccountAsset from GetAccountAssets response
+    :param precision: optional precision of the asset, Iroha formats balances with it anyway
+    :return: Amount
+    """
+    return Amount(account_asset.balance, precision)
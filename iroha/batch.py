@@ -0,0 +1,258 @@
+#!/usr/bin/env python3
+#
+# Copyright Soramitsu Co., Ltd. All Rights Reserved.
+# SPDX-License-Identifier: Apache-2.0
+#
+
+import collections
+
+from . import transaction_pb2
+from .errors import BatchError
+from .iroha import IrohaCrypto
+
+BatchMeta = transaction_pb2.Transaction.Payload.BatchMeta
+ATOMIC = BatchMeta.ATOMIC
+ORDERED = BatchMeta.ORDERED
+
+
+def _hex(value):
+    return value.decode() if isinstance(value, bytes) else value
+
+
+class SignatureRequirement(object):
+    """
+    Signing state of a batch member
+    """
+
+    def __init__(self, index, tx_hash, quorum, signed_keys, missing_keys=None):
+        """
+        :param index: position of the transaction in the batch
+        :param tx_hash: hex hash of the transaction
+        :param quorum: number of signatures the transaction needs
+        :param signed_keys: public keys with valid signatures
+        :param missing_keys: signatories of the creator that have not signed yet, None if unknown
+        """
+        self.index = index
+        self.tx_hash = tx_hash
+        self.quorum = quorum
+        self.signed_keys = signed_keys
+        self.missing_keys = missing_keys
+
+    @property
+    def missing_count(self):
+        """Number of signatures left to reach the quorum"""
+        return max(0, self.quorum - len(self.signed_keys))
+
+    @property
+    def is_satisfied(self):
+        return self.missing_count == 0
+
+    def __repr__(self):
+        return 'SignatureRequirement(index={}, tx_hash={!r}, quorum={}, signed_keys={!r}, missing_keys={!r})'.format(
+            self.index, self.tx_hash, self.quorum, self.signed_keys, self.missing_keys)
+
+
+class Batch(object):
+    """
+    ATOMIC or ORDERED batch of transactions.
+    The batch keeps its own copies of the member transactions and the reduced hashes
+    computed when it was created, so modifications of members are detected by check().
+    """
+
+    def __init__(self, transactions, atomic=True):
+        """
+        Tie unsigned transactions into a batch
+        :param transactions: list of protobuf transactions, they are copied and not modified
+        :param atomic: ATOMIC batch if true, otherwise ORDERED
+        :raise: BatchError if there are no transactions or some of them are already signed
+        """
+        problems = ['transaction {} is already signed'.format(index)
+                    for index, tx in enumerate(transactions) if tx.signatures]
+        if not transactions:
+            problems.append('batch has to contain at least one transaction')
+        if problems:
+            raise BatchError(problems)
+        self._type = ATOMIC if atomic else ORDERED
+        self._transactions = []
+        for tx in transactions:
+            member = transaction_pb2.Transaction()
+            member.CopyFrom(tx)
+            member.payload.ClearField('batch')
+            self._transactions.append(member)
+        self._reduced_hashes = [_hex(IrohaCrypto.reduced_hash(tx)) for tx in self._transactions]
+        for tx in self._transactions:
+            tx.payload.batch.type = self._type
+            tx.payload.batch.reduced_hashes.extend(self._reduced_hashes)
+
+    @classmethod
+    def from_transactions(cls, transactions):
+        """
+        Restore a batch from its member transactions, e.g. received from pending transactions
+        :param transactions: protobuf transactions with batch meta in the batch order
+        :return: Batch
+        :raise: BatchError if the transactions do not form a consistent batch
+        """
+        if not transactions or not transactions[0].payload.HasField('batch'):
+            raise BatchError(['transactions have no batch meta'])
+        batch = cls.__new__(cls)
+        meta = transactions[0].payload.batch
+        batch._type = meta.type
+        batch._reduced_hashes = list(meta.reduced_hashes)
+        batch._transactions = []
+        for tx in transactions:
+            member = transaction_pb2.Transaction()
+            member.CopyFrom(tx)
+            batch._transactions.append(member)
+        batch.check()
+        return batch
+
+    @property
+    def is_atomic(self):
+        return self._type == ATOMIC
+
+    @property
+    def transactions(self):
+        """
+        Member transactions in the batch order, modifications of them are reported by check()
+        """
+        return self._transactions
+
+    @property
+    def reduced_hashes(self):
+        """Hex reduced hashes of the members as recorded in the batch meta"""
+        return list(self._reduced_hashes)
+
+    @property
+    def hashes(self):
+        """Hex hashes of the members used to track their statuses"""
+        return [IrohaCrypto.hash(tx).hex() for tx in self._transactions]
+
+    def problems(self):
+        """
+        Find inconsistencies between the batch and its members
+        :return: list of problem descriptions, empty if the batch is intact
+        """
+        problems = []
+        if len(self._transactions) != len(self._reduced_hashes):
+            problems.append('batch has {} transactions, but {} reduced hashes'.format(
+                len(self._transactions), len(self._reduced_hashes)))
+        for index, tx in enumerate(self._transactions):
+            if not tx.payload.HasField('batch'):
+                problems.append('transaction {} has no batch meta'.format(index))
+                continue
+            if tx.payload.batch.type != self._type \
+                    or list(tx.payload.batch.reduced_hashes) != self._reduced_hashes:
+                problems.append('transaction {} has different batch meta'.format(index))
+            reduced_hash = _hex(IrohaCrypto.reduced_hash(tx))
+            if index >= len(self._reduced_hashes) or reduced_hash != self._reduced_hashes[index]:
+                problems.append('reduced hash of transaction {} does not match the batch'.format(index))
+            for signature in tx.signatures:
+                if not IrohaCrypto.is_any_signature_valid(tx, signature):
+                    problems.append('transaction {} has an invalid signature of {}'.format(
+                        index, signature.public_key))
+        return problems
+
+    def check(self):
+        """
+        :raise: BatchError if the batch or its members have been tampered with
+        """
+        problems = self.problems()
+        if problems:
+            raise BatchError(problems)
+
+    def signature_requirements(self, signatories=None):
+        """
+        Report signatures each member still needs
+        :param signatories: optional dict of account id to its public keys
+        to list keys that have not signed yet
+        :return: list of SignatureRequirement in the batch order
+        """
+        requirements = []
+        for index, tx in enumerate(self._transactions):
+            signed_keys = [_hex(signature.public_key).lower() for signature in tx.signatures
+                           if IrohaCrypto.is_any_signature_valid(tx, signature)]
+            missing_keys = None
+            if signatories is not None:
+                creator = tx.payload.reduced_payload.creator_account_id
+                missing_keys = [key for key in signatories.get(creator, ())
+                                if _hex(key).lower() not in signed_keys]
+            requirements.append(SignatureRequirement(
+                index, IrohaCrypto.hash(tx).hex(), tx.payload.reduced_payload.quorum, signed_keys, missing_keys))
+        return requirements
+
+    @property
+    def is_fully_signed(self):
+        """Whether every member has enough signatures to meet its quorum"""
+        return all(requirement.is_satisfied for requirement in self.signature_requirements())
+
+    def sign(self, *private_keys, indexes=None, creator=None):
+        """
+        Sign members of the batch, a key that has already signed a member is skipped
+        :param private_keys: hex private keys
+        :param indexes: positions of members to sign, all by default
+        :param creator: sign only members created by this account
+        :return: list of indexes of the signed members
+        :raise: BatchError if the batch has been tampered with
+        """
+        assert len(private_keys), 'At least one private key has to be passed'
+        self.check()
+        public_keys = [_hex(IrohaCrypto.derive_public_key(key)).lower() for key in private_keys]
+        selected = range(len(self._transactions)) if indexes is None else indexes
+        signed = []
+        for index in selected:
+            tx = self._transactions[index]
+            if creator is not None and tx.payload.reduced_payload.creator_account_id != str(creator):
+                continue
+            existing = {_hex(signature.public_key).lower() for signature in tx.signatures}
+            keys = [key for key, public_key in zip(private_keys, public_keys) if public_key not in existing]
+            if keys:
+                IrohaCrypto.sign_transaction(tx, *keys)
+                signed.append(index)
+        return signed
+
+    def submit(self, net, timeout=None):
+        """
+        Send the batch with IrohaGrpc.send_txs
+        :param net: IrohaGrpc (or compatible) transport
+        :param timeout: timeout for network I/O operations in seconds
+        :return: hex hashes of the members to track their statuses
+        :raise: BatchError if the batch has been tampered with,
+        grpc.RpcError with .code() available in case of any network error
+        """
+        self.check()
+        net.send_txs(self._transactions, timeout)
+        return self.hashes
+
+    def submit_await(self, net, timeout=None, raise_on_reject=True):
+        """
+        Send the batch and follow status streams of all the members until terminal statuses
+        :param net: IrohaGrpc (or compatible) transport
+        :param timeout: overall time limit in seconds, None means wait forever
+        :param raise_on_reject: raise an exception if any of the members is not committed
+        :return: dict of member hash to TxResult in the batch order
+        :raise: BatchError if the batch has been tampered with,
+        TransactionRejectedError subclass for the first not committed member,
+        TransactionTimeoutError if no terminal status is reached in time
+        """
+        self.check()
+        results = net.send_txs_await(self._transactions, timeout, raise_on_reject)
+        return collections.OrderedDict(zip(self.hashes, results))
+
+    def statuses(self, net, timeout=None):
+        """
+        Request current statuses of all the members
+        :param net: IrohaGrpc (or compatible) transport
+        :param timeout: timeout for network I/O operations in seconds
+        :return: dict of member hash to (status name, status code, error code)
+        """
+        return collections.OrderedDict((tx_hash, net.tx_status(tx, timeout))
+                                       for tx_hash, tx in zip(self.hashes, self._transactions))
+
+    def __len__(self):
+        return len(self._transactions)
+
+    def __iter__(self):
+        return iter(self._transactions)
+
+    def __repr__(self):
+        return 'Batch({}, {} transactions)'.format(BatchMeta.BatchType.Name(self._type), len(self._transactions))
@@ -0,0 +1,269 @@
+#!/usr/bin/env python3
+#
+# Copyright Soramitsu Co., Ltd. All Rights Reserved.
+# SPDX-License-Identifier: Apache-2.0
+#
+
+import collections
+import concurrent.futures
+import os
+import threading
+
+import grpc
+
+from . import qry_responses_pb2
+from .errors import BlockStreamError
+from .iroha import IrohaCrypto, IrohaGrpc
+from .retry import RetryPolicy
+
+# error code of GetBlock query for a height above the top of the chain
+INVALID_HEIGHT_ERROR_CODE = 3
+
+
+class MemoryCheckpointStore(object):
+    """
+    Keeps the last processed block height in memory
+    """
+
+    def __init__(self, height=None):
+        self._height = height
+
+    def load(self):
+        """
+        :return: the last processed height or None if nothing was processed
+        """
+        return self._height
+
+    def save(self, height):
+        """
+        :param height: height of the block that has just been processed
+        """
+        self._height = height
+
+
+class FileCheckpointStore(object):
+    """
+    Keeps the last processed block height in a file
+    """
+
+    def __init__(self, path):
+        """
+        :param path: path of the file to store the height in
+        """
+        self._path = path
+
+    def load(self):
+        """
+        :return: the last processed height or None if nothing was processed
+        """
+        if not os.path.exists(self._path):
+            return None
+        with open(self._path, 'r') as checkpoint_file:
+            stored = checkpoint_file.read().strip()
+        return int(stored) if stored else None
+
+    def save(self, height):
+        """
+        :param height: height of the block that has just been processed
+        """
+        tmp_path = '{}.tmp'.format(self._path)
+        with open(tmp_path, 'w') as checkpoint_file:
+            checkpoint_file.write(str(height))
+        os.replace(tmp_path, self._path)
+
+
+def block_height(block):
+    """
+    :param block: protobuf Block
+    :return: height of the block
+    """
+    return block.block_v1.payload.height
+
+
+def get_block(iroha, net, private_key, height, timeout=None):
+    """
+    Request a single block with GetBlock query
+    :param iroha: Iroha instance used to create the query
+    :param net: IrohaGrpc (or compatible) transport
+    :param private_key: key to sign the query with
+    :param height: height of the block
+    :param timeout: timeout for network I/O operations in seconds
+    :return: protobuf Block or None if the chain is not that high yet
+    :raise: QueryError subclass if Iroha answered with any other ErrorResponse
+    """
+    query = iroha.query('GetBlock', height=height)
+    IrohaCrypto.sign_query(query, private_key)
+    response = net.send_query(query, timeout)
+    if response.HasField('error_response') \
+            and response.error_response.reason == qry_responses_pb2.ErrorResponse.STATEFUL_INVALID \
+            and response.error_response.error_code == INVALID_HEIGHT_ERROR_CODE:
+        return None
+    return IrohaGrpc.unwrap_query_response(response).block
+
+
+class BlockRangeFetcher(object):
+    """
+    Reads history of the chain with parallel GetBlock queries
+    """
+
+    def __init__(self, iroha, net, private_key, concurrency=4, timeout=None):
+        """
+        :param iroha: Iroha instance used to create queries
+        :param net: IrohaGrpc (or compatible) transport
+        :param private_key: key to sign queries with
+        :param concurrency: maximum number of queries in flight
+        :param timeout: timeout for network I/O operations in seconds
+        """
+        assert concurrency >= 1, 'At least one query has to be allowed in flight'
+        self._iroha = iroha
+        self._net = net
+        self._private_key = private_key
+        self._concurrency = concurrency
+        self._timeout = timeout
+
+    def blocks(self, first_height=1, last_height=None, follow=False, **subscription_kwargs):
+        """
+        Iterate over blocks in height order
+        :param first_height: height of the first block
+        :param last_height: optional height of the last block, the top of the chain is default
+        :param follow: continue with newly committed blocks via BlockSubscription
+        once the top of the chain is reached, ignored if last_height is set
+        :param subscription_kwargs: checkpoint_store, retry_policy passed to BlockSubscription,
+        the checkpoint store is moved to the last fetched height before the handoff
+        :return: an iterable over protobuf Block
+        :raise: QueryError subclass if Iroha answered with an unexpected ErrorResponse
+        """
+        next_height = first_height
+        for block in self._range(first_height, last_height):
+            next_height = block_height(block) + 1
+            yield block
+        if follow and last_height is None:
+            checkpoint_store = subscription_kwargs.get('checkpoint_store')
+            if checkpoint_store is not None and next_height > 1:
+                checkpoint_store.save(next_height - 1)
+            subscription = BlockSubscription(
+                self._iroha, self._net, self._private_key, start_height=next_height,
+                timeout=self._timeout, **subscription_kwargs)
+            yield from subscription
+
+    def _range(self, first_height, last_height):
+        with concurrent.futures.ThreadPoolExecutor(max_workers=self._concurrency) as executor:
+            in_flight = collections.deque()
+            next_height = first_height
+            try:
+                while True:
+                    while len(in_flight) < self._concurrency \
+                            and (last_height is None or next_height <= last_height):
+                        in_flight.append(executor.submit(
+                            get_block, self._iroha, self._net, self._private_key,
+                            next_height, self._timeout))
+                        next_height += 1
+                    if not in_flight:
+                        return
+                    block = in_flight.popleft().result()
+                    if block is None:
+                        return
+                    yield block
+            finally:
+                for future in in_flight:
+                    future.cancel()
+
+
+class BlockSubscription(object):
+    """
+    Self-healing subscription to committed blocks.
+    Blocks are delivered in height order without gaps and duplicates:
+    on (re)connection missed blocks are requested with GetBlock queries
+    and then the FetchCommits stream is followed. The height of each block is
+    saved to the checkpoint store after the consumer asks for the next one.
+    """
+
+    def __init__(self, iroha, net, private_key, start_height=None, checkpoint_store=None,
+                 retry_policy=None, timeout=None):
+        """
+        :param iroha: Iroha instance used to create queries
+        :param net: IrohaGrpc (or compatible) transport
+        :param private_key: key to sign queries with
+        :param start_height: height of the first block to deliver if the checkpoint store is empty,
+        None means to start with the next committed block
+        :param checkpoint_store: object with load() and save(height) methods,
+        MemoryCheckpointStore is default
+        :param retry_policy: RetryPolicy defining backoff between reconnections,
+        by default reconnection is attempted forever
+        :param timeout: timeout for network I/O operations in seconds
+        """
+        self._iroha = iroha
+        self._net = net
+        self._private_key = private_key
+        self._store = checkpoint_store if checkpoint_store is not None else MemoryCheckpointStore()
+        self._retry_policy = retry_policy if retry_policy else RetryPolicy(
+            max_attempts=float('inf'), initial_backoff=0.5, max_backoff=30.0)
+        self._timeout = timeout
+        self._closed = threading.Event()
+        self.last_height = self._store.load()
+        if self.last_height is None and start_height is not None:
+            self.last_height = start_height - 1
+
+    def close(self):
+        """
+        Stop the subscription after the block currently being processed
+        """
+        self._closed.set()
+
+    def __iter__(self):
+        failures = 0
+        while not self._closed.is_set():
+            try:
+                for block in self._session():
+                    failures = 0
+                    yield block
+                    self._store.save(block_height(block))
+                    if self._closed.is_set():
+                        return
+                # the stream has been closed by the peer, reconnect after a pause
+                self._retry_policy.pause(1)
+            except grpc.RpcError as rpc_error:
+                failures += 1
+                if not self._retry_policy.is_retryable(rpc_error) \
+                        or failures >= self._retry_policy.max_attempts:
+                    raise
+                self._retry_policy.pause(failures)
+
+    def _session(self):
+        """
+        Deliver missed blocks and then follow the blocks stream until it breaks
+        :return: an iterable over protobuf Block, last_height is updated for each of them
+        """
+        if self.last_height is not None:
+            yield from self._catch_up(None)
+        query = self._iroha.blocks_query()
+        IrohaCrypto.sign_query(query, self._private_key)
+        for response in self._net.send_blocks_stream_query(query, self._timeout):
+            if response.HasField('block_error_response'):
+                raise BlockStreamError(response.block_error_response.message)
+            block = response.block_response.block
+            height = block_height(block)
+            if self.last_height is not None:
+                if height <= self.last_height:
+                    continue
+                yield from self._catch_up(height)
+            self.last_height = height
+            yield block
+
+    def _catch_up(self, end_height):
+        """
+        Request blocks following last_height with GetBlock
+        :param end_height: height to stop before, None means to stop at the top of the chain
+        :return: an iterable over protobuf Block
+        """
+        height = self.last_height + 1
+        while end_height is None or height < end_height:
+            block = get_block(self._iroha, self._net, self._private_key, height, self._timeout)
+            if block is None:
+                if end_height is not None:
+                    raise BlockStreamError(
+                        'Block {} is not available while block {} is committed'.format(height, end_height))
+                return
+            self.last_height = height
+            yield block
+            height += 1
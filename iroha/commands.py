@@ -0,0 +1,446 @@
+#!/usr/bin/env python3
+#
+# Copyright Soramitsu Co., Ltd. All Rights Reserved.
+# SPDX-License-Identifier: Apache-2.0
+#
+
+from . import commands_pb2
+from . import primitive_pb2
+from . import validation
+from .amount import Amount
+from .errors import ValidationError
+from .ids import to_wire
+
+# CamelCased command names mapped to names of fields in protobuf Command
+COMMAND_FIELDS = {field.message_type.name: field.name
+                  for field in commands_pb2.Command.DESCRIPTOR.oneofs_by_name['command'].fields}
+
+
+def _check(field, value, rule):
+    value = to_wire(value)
+    error = rule(value)
+    if error:
+        raise ValidationError(field, error)
+    return value
+
+
+def _optional(field, value, rule):
+    return None if value is None else _check(field, value, rule)
+
+
+def _amount(field, value):
+    if not isinstance(value, str):
+        try:
+            value = str(Amount(value))
+        except ValidationError as error:
+            raise ValidationError(field, error.message)
+    return _check(field, value, validation.amount_error)
+
+
+def _enum_value(field, value, enum_type):
+    if isinstance(value, str):
+        try:
+            return enum_type.Value(value)
+        except ValueError:
+            raise ValidationError(field, 'unknown {} {!r}'.format(enum_type.DESCRIPTOR.name, value))
+    error = validation.enum_error(value, enum_type)
+    if error:
+        raise ValidationError(field, error)
+    return value
+
+
+class CommandBuilder(object):
+    """
+    Base class of typed command builders.
+    Arguments are validated when a builder is created, ids may be passed
+    as AccountId, AssetId, DomainId and RoleId as well as strings.
+    to_proto() produces a protobuf Command accepted by Iroha.transaction.
+    """
+
+    # fields of the schema message in declaration order
+    _fields = ()
+    # fields of oneof groups, None means the field is not set
+    _optional_fields = ()
+
+    @classmethod
+    def command_name(cls):
+        """CamelCased name of the command as in commands.proto"""
+        return cls.__name__
+
+    @classmethod
+    def field_name(cls):
+        """Name of the command field in protobuf Command"""
+        return COMMAND_FIELDS[cls.__name__]
+
+    def to_proto(self):
+        """
+        :return: a proto command
+        """
+        command = commands_pb2.Command()
+        internal_command = getattr(command, self.field_name())
+        internal_command.SetInParent()
+        self._fill(internal_command)
+        return command
+
+    def _fill(self, internal_command):
+        for field in self._fields:
+            value = getattr(self, field)
+            if value is None and field in self._optional_fields:
+                continue
+            setattr(internal_command, field, value)
+
+    @classmethod
+    def _values(cls, internal_command):
+        values = {}
+        for field in cls._fields:
+            if field in cls._optional_fields and not internal_command.HasField(field):
+                values[field] = None
+            else:
+                values[field] = getattr(internal_command, field)
+        return values
+
+    @staticmethod
+    def from_proto(command):
+        """
+        Create a builder from a protobuf Command, no validation is performed
+        :param command: proto command
+        :return: an instance of the matching CommandBuilder subclass
+        :raise: ValueError if the command is empty
+        """
+        field_name = command.WhichOneof('command')
+        if field_name is None:
+            raise ValueError('Command is empty')
+        builder_type = BUILDERS_BY_FIELD[field_name]
+        builder = builder_type.__new__(builder_type)
+        builder.__dict__.update(builder_type._values(getattr(command, field_name)))
+        return builder
+
+    def __eq__(self, other):
+        return type(self) is type(other) and self.__dict__ == other.__dict__
+
+    def __ne__(self, other):
+        return not self == other
+
+    def __repr__(self):
+        return '{}({})'.format(type(self).__name__, ', '.join(
+            '{}={!r}'.format(field, getattr(self, field)) for field in self._fields))
+
+
+class AddAssetQuantity(CommandBuilder):
+    _fields = ('asset_id', 'amount')
+
+    def __init__(self, asset_id: str, amount: "Amount, str, int or Decimal"):
+        """
+        Increase amount of an asset on the balance of the transaction creator
+        :param asset_id: asset id like coin#domain
+        :param amount: positive amount, its precision must not exceed the asset precision
+        """
+        self.asset_id = _check('asset_id', asset_id, validation.asset_id_error)
+        self.amount = _amount('amount', amount)
+
+
+class SubtractAssetQuantity(CommandBuilder):
+    _fields = ('asset_id', 'amount')
+
+    def __init__(self, asset_id: str, amount: "Amount, str, int or Decimal"):
+        """
+        Decrease amount of an asset on the balance of the transaction creator
+        :param asset_id: asset id like coin#domain
+        :param amount: positive amount, its precision must not exceed the asset precision
+        """
+        self.asset_id = _check('asset_id', asset_id, validation.asset_id_error)
+        self.amount = _amount('amount', amount)
+
+
+class AddPeer(CommandBuilder):
+    _fields = ('address', 'peer_key', 'tls_certificate', 'syncing_peer')
+    _optional_fields = ('tls_certificate',)
+
+    def __init__(self, address: str, peer_key: str, tls_certificate: str = None, syncing_peer: bool = False):
+        """
+        Add a peer to the network
+        :param address: internal address of the peer like 127.0.0.1:10001
+        :param peer_key: hex public key of the peer
+        :param tls_certificate: optional PEM-encoded TLS certificate of the peer
+        :param syncing_peer: add the peer as a syncing one, which does not take part in consensus
+        """
+        self.address = _check('address', address, validation.peer_address_error)
+        self.peer_key = _check('peer_key', peer_key, validation.public_key_error)
+        self.tls_certificate = _optional('tls_certificate', tls_certificate, validation.string_error)
+        self.syncing_peer = _check('syncing_peer', syncing_peer, validation.bool_error)
+
+    def _fill(self, internal_command):
+        super()._fill(internal_command.peer)
+
+    @classmethod
+    def _values(cls, internal_command):
+        return super()._values(internal_command.peer)
+
+
+class RemovePeer(CommandBuilder):
+    _fields = ('public_key',)
+
+    def __init__(self, public_key: str):
+        """
+        Remove a peer from the network
+        :param public_key: hex public key of the peer
+        """
+        self.public_key = _check('public_key', public_key, validation.public_key_error)
+
+
+class AddSignatory(CommandBuilder):
+    _fields = ('account_id', 'public_key')
+
+    def __init__(self, account_id: str, public_key: str):
+        """
+        Add a public key to the signatories of an account
+        :param account_id: account id like name@domain
+        :param public_key: hex public key
+        """
+        self.account_id = _check('account_id', account_id, validation.account_id_error)
+        self.public_key = _check('public_key', public_key, validation.public_key_error)
+
+
+class RemoveSignatory(CommandBuilder):
+    _fields = ('account_id', 'public_key')
+
+    def __init__(self, account_id: str, public_key: str):
+        """
+        Remove a public key from the signatories of an account
+        :param account_id: account id like name@domain
+        :param public_key: hex public key
+        """
+        self.account_id = _check('account_id', account_id, validation.account_id_error)
+        self.public_key = _check('public_key', public_key, validation.public_key_error)
+
+
+class CreateAsset(CommandBuilder):
+    _fields = ('asset_name', 'domain_id', 'precision')
+
+    def __init__(self, asset_name: str, domain_id: str, precision: int = 0):
+        """
+        Create an asset in a domain
+        :param asset_name: name of the asset
+        :param domain_id: id of the domain
+        :param precision: number of digits after the decimal point
+        """
+        self.asset_name = _check('asset_name', asset_name, validation.asset_name_error)
+        self.domain_id = _check('domain_id', domain_id, validation.domain_id_error)
+        self.precision = _check('precision', precision, validation.precision_error)
+
+
+class CreateAccount(CommandBuilder):
+    _fields = ('account_name', 'domain_id', 'public_key')
+
+    def __init__(self, account_name: str, domain_id: str, public_key: str):
+        """
+        Create an account in a domain
+        :param account_name: name of the account
+        :param domain_id: id of the domain
+        :param public_key: hex public key of the first signatory
+        """
+        self.account_name = _check('account_name', account_name, validation.account_name_error)
+        self.domain_id = _check('domain_id', domain_id, validation.domain_id_error)
+        self.public_key = _check('public_key', public_key, validation.public_key_error)
+
+
+class SetAccountDetail(CommandBuilder):
+    _fields = ('account_id', 'key', 'value')
+
+    def __init__(self, account_id: str, key: str, value: str):
+        """
+        Set a key-value record in details of an account
+        :param account_id: account id like name@domain
+        :param key: key of the record
+        :param value: value of the record
+        """
+        self.account_id = _check('account_id', account_id, validation.account_id_error)
+        self.key = _check('key', key, validation.detail_key_error)
+        self.value = _check('value', value, validation.detail_value_error)
+
+
+class CompareAndSetAccountDetail(CommandBuilder):
+    _fields = ('account_id', 'key', 'value', 'old_value', 'check_empty')
+    _optional_fields = ('old_value',)
+
+    def __init__(self, account_id: str, key: str, value: str, old_value: str = None, check_empty: bool = False):
+        """
+        Set a key-value record in details of an account if its current value matches the expected one
+        :param account_id: account id like name@domain
+        :param key: key of the record
+        :param value: new value of the record
+        :param old_value: expected current value, None means the record must not exist
+        :param check_empty: treat a missing record as not matching old_value
+        """
+        self.account_id = _check('account_id', account_id, validation.account_id_error)
+        self.key = _check('key', key, validation.detail_key_error)
+        self.value = _check('value', value, validation.detail_value_error)
+        self.old_value = _optional('old_value', old_value, validation.detail_value_error)
+        self.check_empty = _check('check_empty', check_empty, validation.bool_error)
+
+
+class CreateDomain(CommandBuilder):
+    _fields = ('domain_id', 'default_role')
+
+    def __init__(self, domain_id: str, default_role: str):
+        """
+        Create a domain
+        :param domain_id: id of the domain
+        :param default_role: role assigned to accounts created in the domain
+        """
+        self.domain_id = _check('domain_id', domain_id, validation.domain_id_error)
+        self.default_role = _check('default_role', default_role, validation.role_name_error)
+
+
+class SetAccountQuorum(CommandBuilder):
+    _fields = ('account_id', 'quorum')
+
+    def __init__(self, account_id: str, quorum: int):
+        """
+        Set the number of signatures required for transactions of an account
+        :param account_id: account id like name@domain
+        :param quorum: number of signatures
+        """
+        self.account_id = _check('account_id', account_id, validation.account_id_error)
+        self.quorum = _check('quorum', quorum, validation.quorum_error)
+
+
+class TransferAsset(CommandBuilder):
+    _fields = ('src_account_id', 'dest_account_id', 'asset_id', 'description', 'amount')
+
+    def __init__(self, src_account_id: str, dest_account_id: str, asset_id: str,
+                 amount: "Amount, str, int or Decimal", description: str = ''):
+        """
+        Transfer an asset between accounts
+        :param src_account_id: id of the source account
+        :param dest_account_id: id of the destination account
+        :param asset_id: asset id like coin#domain
+        :param amount: positive amount, its precision must not exceed the asset precision
+        :param description: optional message attached to the transfer
+        """
+        self.src_account_id = _check('src_account_id', src_account_id, validation.account_id_error)
+        self.dest_account_id = _check('dest_account_id', dest_account_id, validation.account_id_error)
+        if self.src_account_id == self.dest_account_id:
+            raise ValidationError('dest_account_id', 'source and destination accounts are the same')
+        self.asset_id = _check('asset_id', asset_id, validation.asset_id_error)
+        self.description = _check('description', description, validation.description_error)
+        self.amount = _amount('amount', amount)
+
+
+class AppendRole(CommandBuilder):
+    _fields = ('account_id', 'role_name')
+
+    def __init__(self, account_id: str, role_name: str):
+        """
+        Append a role to an account
+        :param account_id: account id like name@domain
+        :param role_name: name of the role
+        """
+        self.account_id = _check('account_id', account_id, validation.account_id_error)
+        self.role_name = _check('role_name', role_name, validation.role_name_error)
+
+
+class DetachRole(CommandBuilder):
+    _fields = ('account_id', 'role_name')
+
+    def __init__(self, account_id: str, role_name: str):
+        """
+        Detach a role from an account
+        :param account_id: account id like name@domain
+        :param role_name: name of the role
+        """
+        self.account_id = _check('account_id', account_id, validation.account_id_error)
+        self.role_name = _check('role_name', role_name, validation.role_name_error)
+
+
+class CreateRole(CommandBuilder):
+    _fields = ('role_name', 'permissions')
+
+    def __init__(self, role_name: str, permissions: "list of RolePermission values or names" = ()):
+        """
+        Create a role with a set of permissions
+        :param role_name: name of the role
+        :param permissions: RolePermission values or their names, e.g. 'can_transfer'
+        """
+        self.role_name = _check('role_name', role_name, validation.role_name_error)
+        if isinstance(permissions, (str, int)):
+            raise ValidationError('permissions', 'list of permissions expected')
+        self.permissions = [_enum_value('permissions', permission, primitive_pb2.RolePermission)
+                            for permission in permissions]
+
+    def _fill(self, internal_command):
+        internal_command.role_name = self.role_name
+        internal_command.permissions.extend(self.permissions)
+
+    @classmethod
+    def _values(cls, internal_command):
+        return {'role_name': internal_command.role_name, 'permissions': list(internal_command.permissions)}
+
+
+class GrantPermission(CommandBuilder):
+    _fields = ('account_id', 'permission')
+
+    def __init__(self, account_id: str, permission: "GrantablePermission value or name"):
+        """
+        Grant a permission over the transaction creator account to another account
+        :param account_id: id of the account receiving the permission
+        :param permission: GrantablePermission value or its name, e.g. 'can_set_my_quorum'
+        """
+        self.account_id = _check('account_id', account_id, validation.account_id_error)
+        self.permission = _enum_value('permission', permission, primitive_pb2.GrantablePermission)
+
+
+class RevokePermission(CommandBuilder):
+    _fields = ('account_id', 'permission')
+
+    def __init__(self, account_id: str, permission: "GrantablePermission value or name"):
+        """
+        Revoke a previously granted permission
+        :param account_id: id of the account the permission was granted to
+        :param permission: GrantablePermission value or its name, e.g. 'can_set_my_quorum'
+        """
+        self.account_id = _check('account_id', account_id, validation.account_id_error)
+        self.permission = _enum_value('permission', permission, primitive_pb2.GrantablePermission)
+
+
+class SetSettingValue(CommandBuilder):
+    _fields = ('key', 'value')
+
+    def __init__(self, key: str, value: str):
+        """
+        Set a value of a network setting, allowed in the genesis block only
+        :param key: name of the setting, e.g. MaxDescriptionSize
+        :param value: value of the setting
+        """
+        self.key = _check('key', key, validation.string_error)
+        if not key:
+            raise ValidationError('key', 'setting key is empty')
+        self.value = _check('value', value, validation.string_error)
+
+
+class CallEngine(CommandBuilder):
+    _fields = ('type', 'caller', 'callee', 'input')
+    _optional_fields = ('callee',)
+
+    def __init__(self, caller: str, input: str, callee: str = None,
+                 type: int = commands_pb2.CallEngine.kSolidity):
+        """
+        Call a smart contract engine
+        :param caller: account id of the caller
+        :param input: hex encoded input of the call, contract bytecode if callee is not set
+        :param callee: optional hex address of the contract, None deploys a new contract
+        :param type: EngineType value, kSolidity is default
+        """
+        self.type = _enum_value('type', type, commands_pb2.CallEngine.EngineType)
+        self.caller = _check('caller', caller, validation.account_id_error)
+        self.callee = _optional('callee', callee, validation.hex_error)
+        self.input = _check('input', input, validation.hex_error)
+
+
+BUILDERS = (
+    AddAssetQuantity, AddPeer, AddSignatory, AppendRole, CreateAccount, CreateAsset,
+    CreateDomain, CreateRole, DetachRole, GrantPermission, RemoveSignatory, RevokePermission,
+    SetAccountDetail, SetAccountQuorum, SubtractAssetQuantity, TransferAsset, RemovePeer,
+    CompareAndSetAccountDetail, SetSettingValue, CallEngine,
+)
+BUILDERS_BY_FIELD = {builder.field_name(): builder for builder in BUILDERS}
@@ -0,0 +1,451 @@
+#!/usr/bin/env python3
+#
+# Copyright Soramitsu Co., Ltd. All Rights Reserved.
+# SPDX-License-Identifier: Apache-2.0
+#
+
+"""
+Typed model of the Iroha 1.x daemon config file, e.g. docker/iroha/config.docker
+"""
+
+import argparse
+import collections
+import difflib
+import json
+import sys
+
+from .errors import ConfigError
+
+ERROR = 'error'
+WARNING = 'warning'
+
+# keys older daemons used to read, they are ignored by Iroha 1.x
+LEGACY_KEYS = {
+    'load_delay': 'load_delay is not used by Iroha 1.x and is ignored',
+}
+
+
+class ConfigProblem(object):
+    """
+    Problem found in a config, errors prevent the daemon from starting, warnings do not
+    """
+
+    def __init__(self, path, message, severity=ERROR):
+        """
+        :param path: dotted path of the key, e.g. database.port
+        :param message: description of the problem
+        :param severity: ERROR or WARNING
+        """
+        self.path = path
+        self.message = message
+        self.severity = severity
+
+    def __eq__(self, other):
+        return isinstance(other, ConfigProblem) \
+            and (self.path, self.message, self.severity) == (other.path, other.message, other.severity)
+
+    def __ne__(self, other):
+        return not self == other
+
+    def __str__(self):
+        return '{} {}: {}'.format(self.severity, self.path or '<root>', self.message)
+
+    def __repr__(self):
+        return 'ConfigProblem({!r}, {!r}, {!r})'.format(self.path, self.message, self.severity)
+
+
+def _attribute(key):
+    return key.replace(' ', '_')
+
+
+def _join(path, key):
+    return '{}.{}'.format(path, key) if path else key
+
+
+def _type_name(kind):
+    return {int: 'integer', str: 'string', bool: 'boolean', list: 'list', dict: 'object'}.get(kind, 'object')
+
+
+def _has_type(value, kind):
+    if kind is int:
+        return isinstance(value, int) and not isinstance(value, bool)
+    return isinstance(value, kind)
+
+
+def _port_error(value):
+    if _has_type(value, int) and not 1 <= value <= 65535:
+        return 'port {} is out of range [1, 65535]'.format(value)
+    return None
+
+
+class ConfigSection(object):
+    """
+    Base class of config objects. Known keys are attributes (None when absent,
+    spaces in keys are replaced with underscores), unknown keys are kept in `unknown`.
+    Keys of a loaded section are emitted in their original order.
+    """
+
+    # (JSON key, type) in the order keys are emitted
+    _fields = ()
+    _required = ()
+
+    def __init__(self, **values):
+        """
+        :param values: attribute values, names of attributes are JSON keys with underscores instead of spaces
+        """
+        attributes = [_attribute(key) for key, _ in self._fields]
+        unexpected = set(values) - set(attributes)
+        if unexpected:
+            raise TypeError('Unexpected {} attributes: {}'.format(type(self).__name__, ', '.join(sorted(unexpected))))
+        for attribute in attributes:
+            setattr(self, attribute, values.get(attribute))
+        self.unknown = collections.OrderedDict()
+        self._loaded_keys = ()
+
+    @classmethod
+    def from_dict(cls, data):
+        """
+        Values are kept as they are, problems() reports the ones of wrong types
+        :param data: dict parsed from JSON
+        :return: an instance of the section
+        """
+        section = cls()
+        section._loaded_keys = tuple(data)
+        kinds = dict(cls._fields)
+        for key, value in data.items():
+            if key not in kinds:
+                section.unknown[key] = value
+                continue
+            kind = kinds[key]
+            if isinstance(kind, type) and issubclass(kind, ConfigSection) and isinstance(value, dict):
+                value = kind.from_dict(value)
+            setattr(section, _attribute(key), value)
+        return section
+
+    def to_dict(self):
+        """
+        :return: ordered dict ready to be dumped as JSON: loaded keys in their original order,
+        then other known keys in the schema order, then other unknown keys
+        """
+        values = collections.OrderedDict()
+        for key, _ in self._fields:
+            value = getattr(self, _attribute(key))
+            if value is not None:
+                values[key] = value.to_dict() if isinstance(value, ConfigSection) else value
+        values.update(self.unknown)
+        data = collections.OrderedDict((key, values.pop(key)) for key in self._loaded_keys if key in values)
+        data.update(values)
+        return data
+
+    def problems(self, path=''):
+        """
+        :param path: dotted path of the section
+        :return: list of ConfigProblem
+        """
+        problems = []
+        for key, kind in self._fields:
+            value = getattr(self, _attribute(key))
+            key_path = _join(path, key)
+            if value is None:
+                if key in self._required:
+                    problems.append(ConfigProblem(key_path, 'required key is missing'))
+                continue
+            if not _has_type(value, kind):
+                problems.append(ConfigProblem(key_path, '{} expected, got {}'.format(
+                    _type_name(kind), json.dumps(value.to_dict() if isinstance(value, ConfigSection) else value))))
+                continue
+            if isinstance(value, ConfigSection):
+                problems.extend(value.problems(key_path))
+        for key in self.unknown:
+            if not path and key in LEGACY_KEYS:
+                problems.append(ConfigProblem(key, LEGACY_KEYS[key], WARNING))
+            else:
+                problems.append(ConfigProblem(_join(path, key), self._unknown_key_message(key)))
+        problems.extend(self._value_problems(path))
+        return problems
+
+    def _unknown_key_message(self, key):
+        known = [name for name, _ in self._fields if getattr(self, _attribute(name)) is None]
+        matches = difflib.get_close_matches(key, known, n=1)
+        return 'unknown key, did you mean {}?'.format(matches[0]) if matches else 'unknown key'
+
+    def _value_problems(self, path):
+        """Checks of values and their combinations, types are already checked"""
+        return []
+
+    def _valid(self, attribute, kind=int):
+        """Value of an attribute if it has the expected type, None otherwise"""
+        value = getattr(self, attribute)
+        return value if _has_type(value, kind) else None
+
+    def __eq__(self, other):
+        return type(self) is type(other) and dict(_flatten(self.to_dict())) == dict(_flatten(other.to_dict()))
+
+    def __ne__(self, other):
+        return not self == other
+
+    def __repr__(self):
+        return '{}({})'.format(type(self).__name__, json.dumps(self.to_dict()))
+
+
+class DatabaseConfig(ConfigSection):
+    """
+    PostgreSQL connection, replaces pg_opt
+    """
+
+    _fields = (('host', str), ('port', int), ('user', str), ('password', str),
+               ('working database', str), ('maintenance database', str))
+    _required = ('host', 'port', 'user', 'password')
+
+    def _value_problems(self, path):
+        error = _port_error(self.port)
+        return [ConfigProblem(_join(path, 'port'), error)] if error else []
+
+
+class ToriiTlsConfig(ConfigSection):
+    """
+    TLS endpoint of Torii served in addition to the plain one
+    """
+
+    _fields = (('port', int), ('key_pair_path', str))
+    _required = ('port', 'key_pair_path')
+
+    def _value_problems(self, path):
+        error = _port_error(self.port)
+        return [ConfigProblem(_join(path, 'port'), error)] if error else []
+
+
+class InterPeerTlsConfig(ConfigSection):
+    """
+    TLS of connections between peers
+    """
+
+    _fields = (('key_pair_path', str), ('peer_certificates', dict))
+
+
+class UtilityServiceConfig(ConfigSection):
+    """
+    gRPC service used to shut the daemon down gracefully
+    """
+
+    _fields = (('ip', str), ('port', int))
+    _required = ('ip', 'port')
+
+    def _value_problems(self, path):
+        error = _port_error(self.port)
+        return [ConfigProblem(_join(path, 'port'), error)] if error else []
+
+
+class IrohaConfig(ConfigSection):
+    """
+    Config of Iroha 1.x daemon passed with --config
+    """
+
+    _fields = (
+        ('block_store_path', str),
+        ('torii_port', int),
+        ('torii_tls_params', ToriiTlsConfig),
+        ('internal_port', int),
+        ('pg_opt', str),
+        ('database', DatabaseConfig),
+        ('max_proposal_size', int),
+        ('proposal_creation_timeout', int),
+        ('proposal_delay', int),
+        ('vote_delay', int),
+        ('mst_enable', bool),
+        ('mst_expiration_time', int),
+        ('max_rounds_delay', int),
+        ('stale_stream_max_rounds', int),
+        ('initial_peers', list),
+        ('inter_peer_tls', InterPeerTlsConfig),
+        ('utility_service', UtilityServiceConfig),
+        ('healthcheck_port', int),
+        ('metrics', str),
+        ('log', dict),
+        ('crypto', dict),
+    )
+    _required = ('torii_port', 'internal_port', 'max_proposal_size', 'vote_delay')
+
+    @classmethod
+    def load(cls, path):
+        """
+        :param path: path of the config file
+        :return: IrohaConfig, problems() reports what is wrong with it
+        :raise: ConfigError if the file is not a JSON object
+        """
+        with open(path, 'r') as config_file:
+            return cls.from_json(config_file.read())
+
+    @classmethod
+    def from_json(cls, text):
+        """
+        :param text: JSON text of the config
+        :return: IrohaConfig
+        :raise: ConfigError if the text is not a JSON object
+        """
+        try:
+            data = json.loads(text, object_pairs_hook=collections.OrderedDict)
+        except ValueError as error:
+            raise ConfigError([ConfigProblem('', 'malformed JSON: {}'.format(error))])
+        if not isinstance(data, dict):
+            raise ConfigError([ConfigProblem('', 'JSON object expected')])
+        return cls.from_dict(data)
+
+    def to_json(self, indent=2):
+        """
+        :param indent: indentation of the JSON text
+        :return: JSON text of the config
+        """
+        return json.dumps(self.to_dict(), indent=indent)
+
+    def save(self, path):
+        """
+        :param path: path of the config file to write
+        """
+        with open(path, 'w') as config_file:
+            config_file.write(self.to_json())
+            config_file.write('\n')
+
+    def errors(self):
+        """
+        :return: problems of ERROR severity
+        """
+        return [problem for problem in self.problems() if problem.severity == ERROR]
+
+    def check(self):
+        """
+        :raise: ConfigError if the config has errors, warnings are allowed
+        """
+        errors = self.errors()
+        if errors:
+            raise ConfigError(errors)
+
+    def diff(self, other):
+        """
+        :param other: IrohaConfig to compare with
+        :return: list of (dotted key path, value in this config, value in the other one),
+        None stands for a missing key
+        """
+        mine = _flatten(self.to_dict())
+        theirs = _flatten(other.to_dict())
+        keys = list(mine) + [key for key in theirs if key not in mine]
+        return [(key, mine.get(key), theirs.get(key)) for key in keys if mine.get(key) != theirs.get(key)]
+
+    def _value_problems(self, path):
+        problems = []
+        for key in ('torii_port', 'internal_port', 'healthcheck_port'):
+            error = _port_error(getattr(self, key))
+            if error:
+                problems.append(ConfigProblem(key, error))
+        for key in ('max_proposal_size', 'proposal_creation_timeout', 'proposal_delay', 'vote_delay',
+                    'mst_expiration_time', 'max_rounds_delay', 'stale_stream_max_rounds'):
+            value = self._valid(key)
+            if value is not None and value <= 0:
+                problems.append(ConfigProblem(key, 'positive value expected, got {}'.format(value)))
+        if self.proposal_creation_timeout is None and self.proposal_delay is None:
+            problems.append(ConfigProblem('proposal_creation_timeout', 'required key is missing'))
+        elif self.proposal_delay is not None:
+            problems.append(ConfigProblem('proposal_delay', 'deprecated, use proposal_creation_timeout', WARNING))
+        problems.extend(self._database_problems())
+        problems.extend(self._port_clashes())
+        if self._valid('mst_enable', bool) is False and self.mst_expiration_time is not None:
+            problems.append(ConfigProblem('mst_expiration_time', 'has no effect while mst_enable is false', WARNING))
+        peers = self._valid('initial_peers', list)
+        for index, peer in enumerate(peers or ()):
+            if not isinstance(peer, dict) or not isinstance(peer.get('address'), str) \
+                    or not isinstance(peer.get('public_key'), str):
+                problems.append(ConfigProblem('initial_peers.{}'.format(index),
+                                              'object with address and public_key expected'))
+        return problems
+
+    def _database_problems(self):
+        if self.pg_opt is None and self.database is None:
+            return [ConfigProblem('database', 'either database or pg_opt is required')]
+        if self.pg_opt is not None and self.database is not None:
+            return [ConfigProblem('pg_opt', 'pg_opt and database must not be set together')]
+        pg_opt = self._valid('pg_opt', str)
+        if pg_opt is None:
+            return []
+        options = {}
+        for option in pg_opt.split():
+            name, separator, value = option.partition('=')
+            if not separator or not name or not value:
+                return [ConfigProblem('pg_opt', 'key=value pairs expected, got {!r}'.format(option))]
+            options[name] = value
+        missing = [name for name in ('host', 'port', 'user', 'password') if name not in options]
+        if missing:
+            return [ConfigProblem('pg_opt', 'missing {}'.format(', '.join(missing)))]
+        if not options['port'].isdigit():
+            return [ConfigProblem('pg_opt', 'port {!r} is not a number'.format(options['port']))]
+        return []
+
+    def _port_clashes(self):
+        ports = [('torii_port', self._valid('torii_port')), ('internal_port', self._valid('internal_port')),
+                 ('healthcheck_port', self._valid('healthcheck_port'))]
+        for key, section in (('torii_tls_params', ToriiTlsConfig), ('utility_service', UtilityServiceConfig)):
+            value = self._valid(key, section)
+            if value is not None:
+                ports.append(('{}.port'.format(key), value._valid('port')))
+        problems = []
+        seen = {}
+        for key, port in ports:
+            if port is None or _port_error(port):
+                continue
+            if port in seen:
+                problems.append(ConfigProblem(key, 'port {} is already used by {}'.format(port, seen[port])))
+            else:
+                seen[port] = key
+        return problems
+
+
+def _flatten(data, path=''):
+    flat = collections.OrderedDict()
+    for key, value in data.items():
+        key_path = _join(path, key)
+        if isinstance(value, dict) and value:
+            flat.update(_flatten(value, key_path))
+        else:
+            flat[key_path] = value
+    return flat
+
+
+def main(argv=None):
+    """
+    Command line entry point, see --help
+    :param argv: arguments without the program name, sys.argv by default
+    :return: exit code, 1 if a config has errors or the configs differ
+    """
+    parser = argparse.ArgumentParser(prog='python -m iroha.config', description=__doc__.strip())
+    subparsers = parser.add_subparsers(dest='command')
+    subparsers.required = True
+    check_parser = subparsers.add_parser('check', help='report problems of config files')
+    check_parser.add_argument('paths', nargs='+')
+    diff_parser = subparsers.add_parser('diff', help='compare two config files')
+    diff_parser.add_argument('path')
+    diff_parser.add_argument('other_path')
+    format_parser = subparsers.add_parser('format', help='print a config in the canonical key order')
+    format_parser.add_argument('path')
+    arguments = parser.parse_args(argv)
+    try:
+        if arguments.command == 'check':
+            failed = False
+            for path in arguments.paths:
+                problems = IrohaConfig.load(path).problems()
+                for problem in problems:
+                    print('{}: {}'.format(path, problem))
+                failed = failed or any(problem.severity == ERROR for problem in problems)
+            return 1 if failed else 0
+        if arguments.command == 'diff':
+            changes = IrohaConfig.load(arguments.path).diff(IrohaConfig.load(arguments.other_path))
+            for key, value, other_value in changes:
+                print('{}: {} -> {}'.format(key, json.dumps(value), json.dumps(other_value)))
+            return 1 if changes else 0
+        print(IrohaConfig.load(arguments.path).to_json())
+        return 0
+    except ConfigError as error:
+        print('error: {}'.format(error), file=sys.stderr)
+        return 1
+
+
+if __name__ == '__main__':
+    sys.exit(main())
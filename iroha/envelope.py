@@ -0,0 +1,425 @@
+#!/usr/bin/env python3
+#
+# Copyright Soramitsu Co., Ltd. All Rights Reserved.
+# SPDX-License-Identifier: Apache-2.0
+#
+
+"""
+Envelopes carry unsigned and partially signed transactions between machines,
+e.g. to air-gapped signers and back.
+
+Version 1 of the format is a JSON object:
+
+    {
+      "format": "iroha-envelope",
+      "version": 1,
+      "kind": "transaction" or "batch",
+      "transactions": [
+        {
+          "hash": hex sha3-256 hash of the transaction payload,
+          "summary": human-readable description, informative only,
+          "transaction": base64 of the serialized protobuf Transaction without signatures,
+          "signatures": [{"public_key": hex, "signature": hex}, ...]
+        },
+        ...
+      ]
+    }
+
+The same object can be transferred as base64 of its compact JSON,
+or as QR-friendly chunks of that base64 text, one chunk per line:
+
+    IROHA-ENVELOPE:1:<index>/<count>:<crc32 of the whole text>:<part of the text>
+
+Hashes are recomputed from the transactions and all the signatures are verified
+whenever an envelope is decoded, signed, merged or converted back to transactions.
+"""
+
+import argparse
+import base64
+import binascii
+import datetime
+import json
+import sys
+import zlib
+
+from . import endpoint_pb2
+from . import transaction_pb2
+from .commands import CommandBuilder
+from .errors import EnvelopeError
+from .iroha import IrohaCrypto
+
+FORMAT_NAME = 'iroha-envelope'
+FORMAT_VERSION = 1
+CHUNK_PREFIX = 'IROHA-ENVELOPE'
+ENCODINGS = ('json', 'base64', 'qr')
+
+
+def _hex(value):
+    return value.decode() if isinstance(value, bytes) else value
+
+
+def summarize(transaction):
+    """
+    :param transaction: protobuf Transaction
+    :return: human-readable description of the transaction
+    """
+    reduced_payload = transaction.payload.reduced_payload
+    created = datetime.datetime.fromtimestamp(reduced_payload.created_time / 1000.0, datetime.timezone.utc)
+    lines = ['creator {}, quorum {}, created {}'.format(
+        reduced_payload.creator_account_id, reduced_payload.quorum, created.isoformat(timespec='milliseconds'))]
+    if transaction.payload.HasField('batch'):
+        lines.append('{} batch of {} transactions'.format(
+            transaction_pb2.Transaction.Payload.BatchMeta.BatchType.Name(transaction.payload.batch.type),
+            len(transaction.payload.batch.reduced_hashes)))
+    for index, command in enumerate(reduced_payload.commands):
+        lines.append('{}. {!r}'.format(index + 1, CommandBuilder.from_proto(command)))
+    return '\n'.join(lines)
+
+
+class Envelope(object):
+    """
+    Unsigned or partially signed transaction or batch with the hashes expected by the signers
+    """
+
+    def __init__(self, transactions, hashes=None):
+        """
+        :param transactions: list of protobuf transactions, e.g. members of a batch, they are copied
+        :param hashes: expected hex hashes, computed from the transactions when an envelope is exported
+        :raise: EnvelopeError if hashes or signatures do not match the transactions
+        """
+        if not transactions:
+            raise EnvelopeError('Envelope has to contain at least one transaction')
+        self._transactions = []
+        for tx in transactions:
+            copy = transaction_pb2.Transaction()
+            copy.CopyFrom(tx)
+            self._transactions.append(copy)
+        self._hashes = list(hashes) if hashes is not None \
+            else [IrohaCrypto.hash(tx).hex() for tx in self._transactions]
+        self.verify()
+
+    @property
+    def kind(self):
+        return 'batch' if any(tx.payload.HasField('batch') for tx in self._transactions) else 'transaction'
+
+    @property
+    def hashes(self):
+        return list(self._hashes)
+
+    def verify(self):
+        """
+        Recompute hashes and check all the signatures
+        :raise: EnvelopeError describing every mismatch found
+        """
+        problems = []
+        if len(self._hashes) != len(self._transactions):
+            problems.append('{} hashes for {} transactions'.format(len(self._hashes), len(self._transactions)))
+        for index, (tx, expected) in enumerate(zip(self._transactions, self._hashes)):
+            actual = IrohaCrypto.hash(tx).hex()
+            if actual != expected.lower():
+                problems.append('transaction {} has hash {}, {} expected'.format(index, actual, expected))
+            for signature in tx.signatures:
+                if not IrohaCrypto.is_any_signature_valid(tx, signature):
+                    problems.append('transaction {} has an invalid signature of {}'.format(
+                        index, signature.public_key))
+        if problems:
+            raise EnvelopeError('; '.join(problems))
+
+    def signatures(self):
+        """
+        :return: list of public keys that have signed each transaction
+        """
+        return [[signature.public_key for signature in tx.signatures] for tx in self._transactions]
+
+    def summary(self):
+        """
+        :return: human-readable description of the envelope, regenerated from the transactions
+        """
+        parts = []
+        for index, tx in enumerate(self._transactions):
+            parts.append('transaction {} {}\n{}\nsigned by {} of {}: {}'.format(
+                index, self._hashes[index], summarize(tx), len(tx.signatures),
+                tx.payload.reduced_payload.quorum,
+                ', '.join(signature.public_key for signature in tx.signatures) or 'nobody'))
+        return '\n\n'.join(parts)
+
+    def sign(self, *private_keys, indexes=None):
+        """
+        Add signatures to the transactions, keys that have already signed are skipped
+        :param private_keys: hex private keys
+        :param indexes: positions of the transactions to sign, all by default
+        :return: the envelope
+        :raise: EnvelopeError if the envelope does not match its transactions
+        """
+        assert len(private_keys), 'At least one private key has to be passed'
+        self.verify()
+        selected = range(len(self._transactions)) if indexes is None else indexes
+        for index in selected:
+            tx = self._transactions[index]
+            existing = {signature.public_key.lower() for signature in tx.signatures}
+            keys = [key for key in private_keys
+                    if _hex(IrohaCrypto.derive_public_key(key)).lower() not in existing]
+            if keys:
+                IrohaCrypto.sign_transaction(tx, *keys)
+        return self
+
+    def merge(self, *others):
+        """
+        Collect signatures of envelopes with the same transactions
+        :param others: envelopes signed elsewhere
+        :return: a new Envelope with signatures of all the envelopes
+        :raise: EnvelopeError if the envelopes carry different transactions
+        """
+        merged = Envelope(self._transactions, self._hashes)
+        for other in others:
+            if other.hashes != merged.hashes:
+                raise EnvelopeError('Envelopes carry different transactions')
+            other.verify()
+            for tx, other_tx in zip(merged._transactions, other._transactions):
+                existing = {signature.public_key.lower() for signature in tx.signatures}
+                for signature in other_tx.signatures:
+                    if signature.public_key.lower() not in existing:
+                        tx.signatures.add().CopyFrom(signature)
+                        existing.add(signature.public_key.lower())
+        return merged
+
+    def transactions(self):
+        """
+        Import the transactions, e.g. to send them with IrohaGrpc.send_txs
+        :return: list of verified protobuf transactions with the collected signatures
+        :raise: EnvelopeError if the envelope does not match its transactions
+        """
+        self.verify()
+        result = []
+        for tx in self._transactions:
+            copy = transaction_pb2.Transaction()
+            copy.CopyFrom(tx)
+            result.append(copy)
+        return result
+
+    def to_dict(self):
+        entries = []
+        for tx, tx_hash in zip(self._transactions, self._hashes):
+            unsigned = transaction_pb2.Transaction()
+            unsigned.CopyFrom(tx)
+            unsigned.ClearField('signatures')
+            entries.append({
+                'hash': tx_hash,
+                'summary': summarize(tx),
+                'transaction': base64.b64encode(unsigned.SerializeToString()).decode('ascii'),
+                'signatures': [{'public_key': signature.public_key, 'signature': signature.signature}
+                               for signature in tx.signatures],
+            })
+        return {'format': FORMAT_NAME, 'version': FORMAT_VERSION, 'kind': self.kind, 'transactions': entries}
+
+    @classmethod
+    def from_dict(cls, data):
+        """
+        :param data: dict in the envelope format
+        :return: Envelope
+        :raise: EnvelopeError if the data is malformed or does not match the transactions
+        """
+        if not isinstance(data, dict) or data.get('format') != FORMAT_NAME:
+            raise EnvelopeError('Not an {} document'.format(FORMAT_NAME))
+        if data.get('version') != FORMAT_VERSION:
+            raise EnvelopeError('Unsupported envelope version {!r}'.format(data.get('version')))
+        transactions = []
+        hashes = []
+        try:
+            for entry in data['transactions']:
+                tx = transaction_pb2.Transaction()
+                tx.ParseFromString(base64.b64decode(entry['transaction'], validate=True))
+                tx.ClearField('signatures')
+                for signature in entry.get('signatures', ()):
+                    tx.signatures.add(public_key=signature['public_key'], signature=signature['signature'])
+                transactions.append(tx)
+                hashes.append(entry['hash'])
+        except (KeyError, TypeError, ValueError) as error:
+            raise EnvelopeError('Malformed envelope: {}'.format(error))
+        envelope = cls(transactions, hashes)
+        if data.get('kind') != envelope.kind:
+            raise EnvelopeError('Envelope kind {!r} does not match its transactions'.format(data.get('kind')))
+        return envelope
+
+    def to_json(self, indent=2):
+        return json.dumps(self.to_dict(), indent=indent)
+
+    @classmethod
+    def from_json(cls, text):
+        try:
+            data = json.loads(text)
+        except ValueError as error:
+            raise EnvelopeError('Malformed envelope JSON: {}'.format(error))
+        return cls.from_dict(data)
+
+    def to_base64(self):
+        text = json.dumps(self.to_dict(), separators=(',', ':'), sort_keys=True)
+        return base64.b64encode(text.encode('utf-8')).decode('ascii')
+
+    @classmethod
+    def from_base64(cls, text):
+        try:
+            decoded = base64.b64decode(''.join(text.split()), validate=True).decode('utf-8')
+        except (binascii.Error, UnicodeDecodeError) as error:
+            raise EnvelopeError('Malformed base64 envelope: {}'.format(error))
+        return cls.from_json(decoded)
+
+    def to_qr_chunks(self, chunk_size=500):
+        """
+        :param chunk_size: maximum number of base64 characters per chunk
+        :return: list of text chunks, each fits into a single QR code
+        """
+        text = self.to_base64()
+        checksum = '{:08x}'.format(zlib.crc32(text.encode('ascii')))
+        parts = [text[start:start + chunk_size] for start in range(0, len(text), chunk_size)]
+        return ['{}:{}:{}/{}:{}:{}'.format(CHUNK_PREFIX, FORMAT_VERSION, index + 1, len(parts), checksum, part)
+                for index, part in enumerate(parts)]
+
+    @classmethod
+    def from_qr_chunks(cls, chunks):
+        """
+        :param chunks: text chunks in any order
+        :return: Envelope
+        :raise: EnvelopeError if chunks are missing, duplicated or belong to different envelopes
+        """
+        parts = {}
+        counts = set()
+        checksums = set()
+        for chunk in chunks:
+            fields = chunk.strip().split(':', 4)
+            if len(fields) != 5 or fields[0] != CHUNK_PREFIX or fields[1] != str(FORMAT_VERSION):
+                raise EnvelopeError('Malformed envelope chunk {!r}'.format(chunk[:40]))
+            try:
+                index, count = (int(number) for number in fields[2].split('/'))
+            except ValueError:
+                raise EnvelopeError('Malformed envelope chunk number {!r}'.format(fields[2]))
+            if index in parts and parts[index] != fields[4]:
+                raise EnvelopeError('Conflicting envelope chunks number {}'.format(index))
+            parts[index] = fields[4]
+            counts.add(count)
+            checksums.add(fields[3])
+        if len(counts) != 1 or len(checksums) != 1:
+            raise EnvelopeError('Chunks belong to different envelopes')
+        count = counts.pop()
+        missing = sorted(set(range(1, count + 1)) - set(parts))
+        if missing or len(parts) != count:
+            raise EnvelopeError('Missing envelope chunks {}'.format(missing))
+        text = ''.join(parts[index] for index in range(1, count + 1))
+        if '{:08x}'.format(zlib.crc32(text.encode('ascii'))) != checksums.pop():
+            raise EnvelopeError('Envelope chunks checksum mismatch')
+        return cls.from_base64(text)
+
+    def encode(self, encoding='json'):
+        """
+        :param encoding: one of ENCODINGS
+        :return: text of the envelope, QR chunks are separated with newlines
+        """
+        if encoding == 'json':
+            return self.to_json()
+        if encoding == 'base64':
+            return self.to_base64()
+        if encoding == 'qr':
+            return '\n'.join(self.to_qr_chunks())
+        raise ValueError('Unknown envelope encoding {!r}, one of {} expected'.format(encoding, ENCODINGS))
+
+    @classmethod
+    def decode(cls, text):
+        """
+        :param text: envelope in any of ENCODINGS, the encoding is detected
+        :return: Envelope
+        :raise: EnvelopeError if the text is not a valid envelope
+        """
+        text = text.strip()
+        if text.startswith('{'):
+            return cls.from_json(text)
+        if text.startswith(CHUNK_PREFIX):
+            return cls.from_qr_chunks(text.splitlines())
+        return cls.from_base64(text)
+
+    def __repr__(self):
+        return 'Envelope({}, hashes={!r})'.format(self.kind, self._hashes)
+
+
+def _read(path):
+    if path == '-':
+        return sys.stdin.read()
+    with open(path, 'r') as envelope_file:
+        return envelope_file.read()
+
+
+def _write(path, text):
+    if path == '-':
+        sys.stdout.write(text + '\n')
+        return
+    with open(path, 'w') as envelope_file:
+        envelope_file.write(text + '\n')
+
+
+def _private_keys(arguments):
+    keys = list(arguments.key or ())
+    for path in arguments.key_file or ():
+        with open(path, 'r') as key_file:
+            keys.append(key_file.read().strip())
+    if not keys:
+        raise EnvelopeError('At least one --key or --key-file has to be passed')
+    return keys
+
+
+def main(argv=None):
+    """
+    Command line tool to export, inspect, sign, merge and import envelopes
+    :param argv: arguments without the program name, sys.argv by default
+    :return: exit code
+    """
+    parser = argparse.ArgumentParser(prog='python -m iroha.envelope', description=__doc__.strip().split('\n\n')[0])
+    commands = parser.add_subparsers(dest='command')
+    commands.required = True
+
+    export = commands.add_parser('export', help='create an envelope from serialized protobuf transactions')
+    export.add_argument('transactions', nargs='+', help='files with a serialized Transaction each, in batch order')
+    inspect = commands.add_parser('inspect', help='verify an envelope and print its summary')
+    inspect.add_argument('envelope')
+    sign = commands.add_parser('sign', help='add signatures to an envelope')
+    sign.add_argument('envelope')
+    sign.add_argument('--key', action='append', help='hex private key')
+    sign.add_argument('--key-file', action='append', help='file with a hex private key')
+    merge = commands.add_parser('merge', help='collect signatures of several envelopes')
+    merge.add_argument('envelopes', nargs='+')
+    import_ = commands.add_parser('import', help='write verified transactions as a serialized protobuf TxList')
+    import_.add_argument('envelope')
+    import_.add_argument('-o', '--output', required=True, help='file for the serialized TxList')
+    for command in (export, sign, merge):
+        command.add_argument('-o', '--output', default='-', help='output file, stdout by default')
+        command.add_argument('--encoding', choices=ENCODINGS, default='json')
+
+    arguments = parser.parse_args(argv)
+    try:
+        if arguments.command == 'export':
+            transactions = []
+            for path in arguments.transactions:
+                tx = transaction_pb2.Transaction()
+                with open(path, 'rb') as tx_file:
+                    tx.ParseFromString(tx_file.read())
+                transactions.append(tx)
+            _write(arguments.output, Envelope(transactions).encode(arguments.encoding))
+        elif arguments.command == 'inspect':
+            print(Envelope.decode(_read(arguments.envelope)).summary())
+        elif arguments.command == 'sign':
+            envelope = Envelope.decode(_read(arguments.envelope)).sign(*_private_keys(arguments))
+            _write(arguments.output, envelope.encode(arguments.encoding))
+        elif arguments.command == 'merge':
+            envelopes = [Envelope.decode(_read(path)) for path in arguments.envelopes]
+            _write(arguments.output, envelopes[0].merge(*envelopes[1:]).encode(arguments.encoding))
+        elif arguments.command == 'import':
+            tx_list = endpoint_pb2.TxList()
+            tx_list.transactions.extend(Envelope.decode(_read(arguments.envelope)).transactions())
+            with open(arguments.output, 'wb') as tx_list_file:
+                tx_list_file.write(tx_list.SerializeToString())
+    except EnvelopeError as error:
+        print('error: {}'.format(error), file=sys.stderr)
+        return 1
+    return 0
+
+
+if __name__ == '__main__':
+    sys.exit(main())
@@ -0,0 +1,284 @@
+#!/usr/bin/env python3
+#
+# Copyright Soramitsu Co., Ltd. All Rights Reserved.
+# SPDX-License-Identifier: Apache-2.0
+#
+
+from . import qry_responses_pb2
+
+
+class IrohaError(Exception):
+    """
+    Base class for all the errors raised by the library
+    """
+
+
+class TransactionError(IrohaError):
+    """
+    Transaction has not reached COMMITTED status
+    """
+
+    def __init__(self, result, message=None):
+        """
+        :param result: TxResult with the status history collected so far
+        :param message: optional human-readable description
+        """
+        if message is None:
+            message = 'Transaction {} finished with status {}'.format(
+                result.tx_hash, result.status)
+            if result.err_or_cmd_name:
+                message += ' at command #{} "{}"'.format(
+                    result.failed_cmd_index, result.err_or_cmd_name)
+            if result.error_code:
+                message += ', error code {}'.format(result.error_code)
+        super().__init__(message)
+        self.result = result
+
+    @property
+    def tx_hash(self):
+        return self.result.tx_hash
+
+    @property
+    def error_code(self):
+        return self.result.error_code
+
+    @property
+    def err_or_cmd_name(self):
+        return self.result.err_or_cmd_name
+
+    @property
+    def failed_cmd_index(self):
+        return self.result.failed_cmd_index
+
+
+class TransactionRejectedError(TransactionError):
+    """
+    Transaction has reached a terminal status other than COMMITTED
+    """
+
+
+class StatelessValidationFailedError(TransactionRejectedError):
+    """
+    Transaction has got STATELESS_VALIDATION_FAILED status
+    """
+
+
+class StatefulValidationFailedError(TransactionRejectedError):
+    """
+    Transaction has got STATEFUL_VALIDATION_FAILED status
+    """
+
+
+class RejectedError(TransactionRejectedError):
+    """
+    Transaction has got REJECTED status
+    """
+
+
+class MstExpiredError(TransactionRejectedError):
+    """
+    Multi-signature transaction has got MST_EXPIRED status
+    """
+
+
+class TransactionTimeoutError(TransactionError, TimeoutError):
+    """
+    Transaction has not reached a terminal status in time
+    """
+
+    def __init__(self, result, timeout):
+        super().__init__(result, 'Transaction {} has not reached a terminal status in {} seconds, last status is {}'.format(
+            result.tx_hash, timeout, result.status))
+        self.timeout = timeout
+
+
+TX_STATUS_ERRORS = {
+    'STATELESS_VALIDATION_FAILED': StatelessValidationFailedError,
+    'STATEFUL_VALIDATION_FAILED': StatefulValidationFailedError,
+    'REJECTED': RejectedError,
+    'MST_EXPIRED': MstExpiredError,
+}
+
+
+class BlockStreamError(IrohaError):
+    """
+    Blocks stream has been answered with BlockErrorResponse
+    """
+
+    def __init__(self, message):
+        super().__init__(message)
+        self.message = message
+
+
+class QueryError(IrohaError):
+    """
+    Query has been answered with ErrorResponse
+    """
+
+    def __init__(self, reason, message, error_code=0):
+        """
+        :param reason: symbolic name of ErrorResponse.Reason
+        :param message: error description provided by Iroha
+        :param error_code: error code provided by Iroha
+        """
+        super().__init__('{}: {} (error code {})'.format(reason, message, error_code))
+        self.reason = reason
+        self.message = message
+        self.error_code = error_code
+
+    @staticmethod
+    def from_error_response(error_response):
+        """
+        Create an exception of a type matching the reason of the response
+        :param error_response: protobuf ErrorResponse
+        :return: QueryError subclass instance
+        """
+        reason = qry_responses_pb2.ErrorResponse.Reason.Name(error_response.reason)
+        error_type = QUERY_ERRORS.get(reason, QueryError)
+        return error_type(reason, error_response.message, error_response.error_code)
+
+
+class StatelessInvalidError(QueryError):
+    """
+    Query has not passed stateless validation
+    """
+
+
+class StatefulInvalidError(QueryError):
+    """
+    Query has not passed stateful validation, e.g. the creator lacks permissions
+    """
+
+
+class NoAccountError(QueryError):
+    """
+    Requested account does not exist
+    """
+
+
+class NoAccountAssetsError(QueryError):
+    """
+    Requested account asset does not exist
+    """
+
+
+class NoAccountDetailError(QueryError):
+    """
+    Requested account detail does not exist
+    """
+
+
+class NoSignatoriesError(QueryError):
+    """
+    Requested signatories do not exist
+    """
+
+
+class NotSupportedError(QueryError):
+    """
+    Query type is not supported by the peer
+    """
+
+
+class NoAssetError(QueryError):
+    """
+    Requested asset does not exist
+    """
+
+
+class NoRolesError(QueryError):
+    """
+    There are no roles defined in the system
+    """
+
+
+QUERY_ERRORS = {
+    'STATELESS_INVALID': StatelessInvalidError,
+    'STATEFUL_INVALID': StatefulInvalidError,
+    'NO_ACCOUNT': NoAccountError,
+    'NO_ACCOUNT_ASSETS': NoAccountAssetsError,
+    'NO_ACCOUNT_DETAIL': NoAccountDetailError,
+    'NO_SIGNATORIES': NoSignatoriesError,
+    'NOT_SUPPORTED': NotSupportedError,
+    'NO_ASSET': NoAssetError,
+    'NO_ROLES': NoRolesError,
+}
+
+
+class UnexpectedQueryResponseError(IrohaError, ValueError):
+    """
+    Query response is empty or is not of the expected type, detected by the client
+    """
+
+
+class ValidationError(IrohaError, ValueError):
+    """
+    Argument does not pass client-side validation
+    """
+
+    def __init__(self, field, message):
+        """
+        :param field: name of the invalid argument
+        :param message: description of the problem
+        """
+        super().__init__('{}: {}'.format(field, message))
+        self.field = field
+        self.message = message
+
+
+class StatelessValidationError(IrohaError, ValueError):
+    """
+    Transaction or query does not pass client-side stateless validation
+    """
+
+    def __init__(self, findings):
+        """
+        :param findings: list of iroha.validation.Finding
+        """
+        super().__init__('; '.join(str(finding) for finding in findings))
+        self.findings = findings
+
+
+class BatchError(IrohaError, ValueError):
+    """
+    Batch is malformed or its transactions have been modified after they were tied together
+    """
+
+    def __init__(self, problems):
+        """
+        :param problems: list of problem descriptions
+        """
+        super().__init__('; '.join(problems))
+        self.problems = problems
+
+
+class EnvelopeError(IrohaError, ValueError):
+    """
+    Signing envelope is malformed or does not match the transactions it carries
+    """
+
+
+class GenesisError(IrohaError, ValueError):
+    """
+    Genesis block contents are not consistent
+    """
+
+    def __init__(self, problems):
+        """
+        :param problems: list of problem descriptions
+        """
+        super().__init__('; '.join(problems))
+        self.problems = problems
+
+
+class ConfigError(IrohaError, ValueError):
+    """
+    Iroha daemon config is malformed or invalid
+    """
+
+    def __init__(self, problems):
+        """
+        :param problems: list of iroha.config.ConfigProblem or descriptions
+        """
+        super().__init__('; '.join(str(problem) for problem in problems))
+        self.problems = problems
@@ -0,0 +1,268 @@
+#!/usr/bin/env python3
+#
+# Copyright Soramitsu Co., Ltd. All Rights Reserved.
+# SPDX-License-Identifier: Apache-2.0
+#
+
+import collections
+
+from . import block_pb2
+from . import commands
+from . import protojson
+from . import transaction_pb2
+from .amount import Amount
+from .errors import GenesisError, ValidationError
+from .ids import AccountId, AssetId, to_wire
+
+EMPTY_HASH = '0' * 64
+# roles of docker/iroha/genesis.block
+EXAMPLE_ROLES = collections.OrderedDict([
+    ('admin', ['can_add_peer', 'can_add_signatory', 'can_create_account', 'can_create_domain',
+               'can_get_all_acc_ast', 'can_get_all_acc_ast_txs', 'can_get_all_acc_detail',
+               'can_get_all_acc_txs', 'can_get_all_accounts', 'can_get_all_signatories',
+               'can_get_all_txs', 'can_get_blocks', 'can_get_roles', 'can_read_assets',
+               'can_remove_signatory', 'can_set_quorum']),
+    ('user', ['can_add_signatory', 'can_get_my_acc_ast', 'can_get_my_acc_ast_txs',
+              'can_get_my_acc_detail', 'can_get_my_acc_txs', 'can_get_my_account',
+              'can_get_my_signatories', 'can_get_my_txs', 'can_grant_can_add_my_signatory',
+              'can_grant_can_remove_my_signatory', 'can_grant_can_set_my_account_detail',
+              'can_grant_can_set_my_quorum', 'can_grant_can_transfer_my_assets', 'can_receive',
+              'can_remove_signatory', 'can_set_quorum', 'can_transfer']),
+    ('money_creator', ['can_add_asset_qty', 'can_create_asset', 'can_receive', 'can_transfer']),
+])
+
+
+def _hex(value):
+    return value.decode('utf-8') if isinstance(value, bytes) else to_wire(value)
+
+
+class _Account(object):
+
+    def __init__(self, account_id, public_keys, roles, quorum, details, balances):
+        self.account_id = account_id
+        self.public_keys = public_keys
+        self.roles = roles
+        self.quorum = quorum
+        self.details = details
+        self.balances = balances
+
+
+class GenesisBuilder(object):
+    """
+    Assembles the genesis block of a network from peers, roles, domains, assets and accounts.
+    Arguments are validated as command builders do, the consistency of the whole block
+    is checked by problems() and before the block is built.
+    """
+
+    def __init__(self):
+        self._peers = []
+        self._roles = collections.OrderedDict()
+        self._domains = collections.OrderedDict()
+        self._assets = collections.OrderedDict()
+        self._accounts = collections.OrderedDict()
+        self._duplicates = []
+
+    def _add(self, registry, key, value, entity):
+        if key in registry:
+            self._duplicates.append('{} {} is added twice'.format(entity, key))
+        registry[key] = value
+
+    def add_peer(self, address, peer_key, tls_certificate=None, syncing_peer=False):
+        """
+        :param address: internal address of the peer like 127.0.0.1:10001
+        :param peer_key: hex public key of the peer
+        :param tls_certificate: optional PEM-encoded TLS certificate of the peer
+        :param syncing_peer: add the peer as a syncing one, which does not take part in consensus
+        :return: the builder
+        """
+        self._peers.append(commands.AddPeer(address, _hex(peer_key), tls_certificate, syncing_peer))
+        return self
+
+    def add_role(self, role_name, permissions):
+        """
+        :param role_name: name of the role
+        :param permissions: RolePermission values or their names, e.g. 'can_transfer'
+        :return: the builder
+        """
+        command = commands.CreateRole(role_name, permissions)
+        self._add(self._roles, command.role_name, command, 'role')
+        return self
+
+    def add_domain(self, domain_id, default_role):
+        """
+        :param domain_id: id of the domain
+        :param default_role: role assigned to accounts of the domain, it has to be added as well
+        :return: the builder
+        """
+        command = commands.CreateDomain(domain_id, default_role)
+        self._add(self._domains, command.domain_id, command, 'domain')
+        return self
+
+    def add_asset(self, asset_id, precision=0):
+        """
+        :param asset_id: AssetId or asset id like coin#test, the domain has to be added as well
+        :param precision: number of digits after the decimal point
+        :return: the builder
+        """
+        asset_id = AssetId.coerce(asset_id)
+        self._add(self._assets, str(asset_id),
+                  commands.CreateAsset(asset_id.name, asset_id.domain, precision), 'asset')
+        return self
+
+    def add_account(self, account_id, public_keys, roles=(), quorum=1, details=None, balances=None):
+        """
+        :param account_id: AccountId or account id like alice@test, the domain has to be added as well
+        :param public_keys: hex public key or a list of them, the first one creates the account
+        :param roles: roles appended in addition to the default role of the domain
+        :param quorum: number of signatures required for transactions of the account
+        :param details: optional {key: value} dict written to details of the account by the account itself
+        :param balances: optional {asset id: amount} dict of initial balances
+        :return: the builder
+        """
+        account_id = AccountId.coerce(account_id)
+        if isinstance(public_keys, (str, bytes)):
+            public_keys = [public_keys]
+        public_keys = [_hex(key) for key in public_keys]
+        if not public_keys:
+            raise ValidationError('public_keys', 'at least one public key is required')
+        for key in public_keys[1:]:
+            commands.AddSignatory(account_id, key)
+        commands.CreateAccount(account_id.name, account_id.domain, public_keys[0])
+        for role in roles:
+            commands.AppendRole(account_id, role)
+        commands.SetAccountQuorum(account_id, quorum)
+        details = collections.OrderedDict(details or {})
+        for key, value in details.items():
+            commands.SetAccountDetail(account_id, key, value)
+        balances = collections.OrderedDict((str(asset), amount) for asset, amount in (balances or {}).items())
+        for asset_id, amount in balances.items():
+            commands.AddAssetQuantity(asset_id, amount)
+        self._add(self._accounts, str(account_id),
+                  _Account(account_id, public_keys, [to_wire(role) for role in roles], quorum, details, balances),
+                  'account')
+        return self
+
+    def problems(self):
+        """
+        Check that the block is internally consistent
+        :return: list of problem descriptions, empty if the block can be built
+        """
+        problems = list(self._duplicates)
+        if not [peer for peer in self._peers if not peer.syncing_peer]:
+            problems.append('at least one peer taking part in consensus is required')
+        peer_keys = [peer.peer_key for peer in self._peers]
+        problems.extend('peer key {} is used twice'.format(key)
+                        for key in sorted(set(key for key in peer_keys if peer_keys.count(key) > 1)))
+        for domain in self._domains.values():
+            if domain.default_role not in self._roles:
+                problems.append('default role {} of domain {} is not added'.format(
+                    domain.default_role, domain.domain_id))
+        for asset_id, asset in self._assets.items():
+            if asset.domain_id not in self._domains:
+                problems.append('domain {} of asset {} is not added'.format(asset.domain_id, asset_id))
+        for account_id, account in self._accounts.items():
+            if str(account.account_id.domain) not in self._domains:
+                problems.append('domain {} of account {} is not added'.format(account.account_id.domain, account_id))
+            problems.extend('role {} of account {} is not added'.format(role, account_id)
+                            for role in account.roles if role not in self._roles)
+            if len(set(account.public_keys)) != len(account.public_keys):
+                problems.append('account {} has duplicate public keys'.format(account_id))
+            if account.quorum > len(set(account.public_keys)):
+                problems.append('quorum {} of account {} exceeds the number of its public keys'.format(
+                    account.quorum, account_id))
+            for asset_id, amount in account.balances.items():
+                if asset_id not in self._assets:
+                    problems.append('asset {} of account {} balance is not added'.format(asset_id, account_id))
+                    continue
+                try:
+                    Amount(amount, self._assets[asset_id].precision)
+                except ValidationError as error:
+                    problems.append('balance of {} on account {}: {}'.format(asset_id, account_id, error.message))
+        return problems
+
+    def check(self):
+        """
+        :raise: GenesisError if the block is not consistent
+        """
+        problems = self.problems()
+        if problems:
+            raise GenesisError(problems)
+
+    def transactions(self):
+        """
+        :return: list of genesis protobuf transactions: the first one creates the network entities,
+        a transaction of each account with details or balances follows
+        """
+        self.check()
+        setup = list(self._peers) + list(self._roles.values()) + list(self._domains.values()) \
+            + list(self._assets.values())
+        for account in self._accounts.values():
+            setup.append(commands.CreateAccount(account.account_id.name, account.account_id.domain,
+                                                account.public_keys[0]))
+        for account in self._accounts.values():
+            setup.extend(commands.AddSignatory(account.account_id, key) for key in account.public_keys[1:])
+            setup.extend(commands.AppendRole(account.account_id, role) for role in account.roles)
+            if account.quorum != 1:
+                setup.append(commands.SetAccountQuorum(account.account_id, account.quorum))
+        transactions = [self._transaction(setup)]
+        for account in self._accounts.values():
+            seed = [commands.SetAccountDetail(account.account_id, key, value)
+                    for key, value in account.details.items()]
+            seed.extend(commands.AddAssetQuantity(asset_id, amount) for asset_id, amount in account.balances.items())
+            if seed:
+                transactions.append(self._transaction(seed, str(account.account_id)))
+        return transactions
+
+    @staticmethod
+    def _transaction(builders, creator_account_id=None):
+        tx = transaction_pb2.Transaction()
+        tx.payload.reduced_payload.commands.extend(builder.to_proto() for builder in builders)
+        if creator_account_id is not None:
+            tx.payload.reduced_payload.creator_account_id = creator_account_id
+        tx.payload.reduced_payload.quorum = 1
+        return tx
+
+    def build(self, created_time=0):
+        """
+        :param created_time: creation time of the block in milliseconds, zero as in the example genesis.block
+        :return: protobuf Block with a single Block_v1 at height 1
+        :raise: GenesisError if the block is not consistent
+        """
+        block = block_pb2.Block()
+        payload = block.block_v1.payload
+        payload.transactions.extend(self.transactions())
+        payload.tx_number = len(payload.transactions)
+        payload.height = 1
+        payload.prev_block_hash = EMPTY_HASH
+        payload.created_time = created_time
+        return block
+
+    def save(self, path, created_time=0):
+        """
+        Write the block as the JSON file Iroha daemon loads with --genesis_block
+        :param path: path of the file
+        :param created_time: creation time of the block in milliseconds
+        :raise: GenesisError if the block is not consistent
+        """
+        protojson.save(path, self.build(created_time))
+
+
+def example_genesis(peers, admin_public_key, test_public_key):
+    """
+    Contents of docker/iroha/genesis.block for any set of peers:
+    the example roles, domain test with asset coin#test, accounts admin@test and test@test
+    :param peers: list of (internal address, hex public key) of the peers
+    :param admin_public_key: hex public key of admin@test
+    :param test_public_key: hex public key of test@test
+    :return: GenesisBuilder, more entities can be added to it
+    """
+    builder = GenesisBuilder()
+    for address, peer_key in peers:
+        builder.add_peer(address, peer_key)
+    for role_name, permissions in EXAMPLE_ROLES.items():
+        builder.add_role(role_name, permissions)
+    return builder \
+        .add_domain('test', 'user') \
+        .add_asset('coin#test', 2) \
+        .add_account('admin@test', admin_public_key, roles=['admin', 'money_creator']) \
+        .add_account('test@test', test_public_key)
@@ -0,0 +1,204 @@
+#!/usr/bin/env python3
+#
+# Copyright Soramitsu Co., Ltd. All Rights Reserved.
+# SPDX-License-Identifier: Apache-2.0
+#
+
+from . import validation
+from .amount import Amount
+from .errors import ValidationError
+
+
+def _checked(field, value, rule):
+    error = rule(value)
+    if error:
+        raise ValidationError(field, error)
+    return value
+
+
+class Identifier(object):
+    """
+    Base class of parsed and validated entity ids.
+    Instances are immutable, hashable and converted to the wire format with str().
+    """
+
+    __slots__ = ()
+
+    @classmethod
+    def parse(cls, value):
+        """
+        Create an id from its string representation
+        :param value: string like 'admin@test'
+        :return: an instance of the class
+        :raise: ValidationError if the string is not a valid id
+        """
+        raise NotImplementedError
+
+    @classmethod
+    def coerce(cls, value):
+        """
+        :param value: an instance of the class or its string representation
+        :return: an instance of the class
+        :raise: ValidationError if the string is not a valid id
+        """
+        if isinstance(value, cls):
+            return value
+        if not isinstance(value, str):
+            raise ValidationError(cls.__name__, 'string or {} expected, got {}'.format(
+                cls.__name__, type(value).__name__))
+        return cls.parse(value)
+
+    def _key(self):
+        raise NotImplementedError
+
+    def __setattr__(self, name, value):
+        raise AttributeError('{} is immutable'.format(type(self).__name__))
+
+    def __eq__(self, other):
+        return type(self) is type(other) and self._key() == other._key()
+
+    def __ne__(self, other):
+        return not self == other
+
+    def __hash__(self):
+        return hash((type(self).__name__,) + self._key())
+
+    def __repr__(self):
+        return '{}({!r})'.format(type(self).__name__, str(self))
+
+
+class DomainId(Identifier):
+    """
+    Id of a domain, e.g. 'test'
+    """
+
+    __slots__ = ('_name',)
+
+    def __init__(self, name: str):
+        """
+        :param name: domain name
+        :raise: ValidationError if the name does not match the domain grammar
+        """
+        object.__setattr__(self, '_name', _checked('domain_id', name, validation.domain_id_error))
+
+    @classmethod
+    def parse(cls, value):
+        return cls(value)
+
+    @property
+    def name(self):
+        return self._name
+
+    def _key(self):
+        return self._name,
+
+    def __str__(self):
+        return self._name
+
+
+class RoleId(Identifier):
+    """
+    Id of a role, e.g. 'admin'
+    """
+
+    __slots__ = ('_name',)
+
+    def __init__(self, name: str):
+        """
+        :param name: role name
+        :raise: ValidationError if the name does not match the role grammar
+        """
+        object.__setattr__(self, '_name', _checked('role_id', name, validation.role_name_error))
+
+    @classmethod
+    def parse(cls, value):
+        return cls(value)
+
+    @property
+    def name(self):
+        return self._name
+
+    def _key(self):
+        return self._name,
+
+    def __str__(self):
+        return self._name
+
+
+class _DomainScopedId(Identifier):
+    """
+    Id consisting of a name and a domain joined with a separator
+    """
+
+    __slots__ = ('_name', '_domain')
+    _separator = None
+    _field = None
+    _name_rule = None
+
+    def __init__(self, name: str, domain: "DomainId or str"):
+        """
+        :param name: name part of the id
+        :param domain: DomainId or domain name
+        :raise: ValidationError if any of the parts is invalid
+        """
+        object.__setattr__(self, '_name', _checked(self._field, name, type(self)._name_rule))
+        object.__setattr__(self, '_domain', DomainId.coerce(domain))
+
+    @classmethod
+    def parse(cls, value):
+        _checked(cls._field, value, validation.string_error)
+        name, separator, domain = value.partition(cls._separator)
+        if not separator:
+            raise ValidationError(cls._field, '{!r} is not a valid {}, no {!r} separator'.format(
+                value, cls._field.replace('_', ' '), cls._separator))
+        return cls(name, domain)
+
+    @property
+    def name(self):
+        return self._name
+
+    @property
+    def domain(self):
+        """DomainId of the entity"""
+        return self._domain
+
+    def _key(self):
+        return self._name, self._domain.name
+
+    def __str__(self):
+        return '{}{}{}'.format(self._name, self._separator, self._domain)
+
+
+class AccountId(_DomainScopedId):
+    """
+    Id of an account, e.g. 'admin@test'
+    """
+
+    __slots__ = ()
+    _separator = '@'
+    _field = 'account_id'
+    _name_rule = staticmethod(validation.account_name_error)
+
+
+class AssetId(_DomainScopedId):
+    """
+    Id of an asset, e.g. 'coin#test'
+    """
+
+    __slots__ = ()
+    _separator = '#'
+    _field = 'asset_id'
+    _name_rule = staticmethod(validation.asset_name_error)
+
+
+def to_wire(value):
+    """
+    Convert ids and amounts to strings expected by protobuf messages, other values are returned as is
+    :param value: any value, lists of ids are converted element-wise
+    :return: the converted value
+    """
+    if isinstance(value, (Identifier, Amount)):
+        return str(value)
+    if isinstance(value, (list, tuple)) and any(isinstance(item, Identifier) for item in value):
+        return [to_wire(item) for item in value]
+    return value
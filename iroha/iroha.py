@@ -12,6 +12,9 @@ import grpc
 import time
 import re
 import os
+import threading
+from google.protobuf import empty_pb2
+from google.protobuf import message as protobuf_message
 
 from . import commands_pb2
 from . import endpoint_pb2
@@ -19,6 +22,12 @@ from . import endpoint_pb2_grpc
 from . import primitive_pb2
 from . import queries_pb2
 from . import transaction_pb2
+from .commands import CommandBuilder
+from .ids import to_wire
+from .errors import TX_STATUS_ERRORS, QueryError, TransactionTimeoutError, UnexpectedQueryResponseError
+
+TERMINAL_TX_STATUSES = ('COMMITTED', 'REJECTED', 'STATEFUL_VALIDATION_FAILED',
+                        'STATELESS_VALIDATION_FAILED', 'MST_EXPIRED')
 
 
 class IrohaCrypto(object):
@@ -160,6 +169,21 @@ class IrohaCrypto(object):
         except (ed25519_sha3.SignatureMismatch, ValueError):
             return False
 
+    @staticmethod
+    def is_any_signature_valid(message, signature):
+        """
+        Verify signature validity choosing sha2 or sha3 check by the public key type
+        :param signature: the signature to be checked
+        :param message: message to check the signature against
+        :return: bool, whether the signature is valid for the message, False if it cannot be checked
+        """
+        try:
+            if signature.public_key.startswith('ed0120'):
+                return IrohaCrypto.is_sha2_signature_valid(message, signature)
+            return IrohaCrypto.is_signature_valid(message, signature)
+        except Exception:
+            return False
+
     @staticmethod
     def reduced_hash(transaction):
         """
@@ -181,13 +205,66 @@ class IrohaCrypto(object):
         return binascii.b2a_hex(os.urandom(32))
 
 
+class QueryCounter(object):
+    """
+    Thread-safe monotonically increasing source of query counters.
+    Optionally the last issued value is stored in a file, so counting
+    continues from it after process restart.
+    """
+
+    def __init__(self, start=1, path=None):
+        """
+        :param start: the first value to be issued
+        :param path: optional path of a file to persist the last issued value in
+        """
+        self._lock = threading.Lock()
+        self._path = path
+        self._next = start
+        self._last = None
+        if path and os.path.exists(path):
+            with open(path, 'r') as counter_file:
+                stored = counter_file.read().strip()
+            if stored:
+                self._next = max(start, int(stored) + 1)
+
+    def next(self):
+        """
+        :return: a new counter value, greater than all previously issued ones
+        """
+        with self._lock:
+            value = self._next
+            self._next += 1
+            self._last = value
+            if self._path:
+                self._persist(value)
+            return value
+
+    @property
+    def last(self):
+        """The last issued value or None if nothing was issued in this session"""
+        with self._lock:
+            return self._last
+
+    def _persist(self, value):
+        tmp_path = '{}.tmp'.format(self._path)
+        with open(tmp_path, 'w') as counter_file:
+            counter_file.write(str(value))
+        os.replace(tmp_path, self._path)
+
+
 class Iroha(object):
     """
     Collection of factory methods for transactions and queries creation
     """
 
-    def __init__(self, creator_account=None):
-        self.creator_account = creator_account
+    def __init__(self, creator_account=None, query_counter=None):
+        """
+        :param creator_account: default account id of transactions and queries creator, str or AccountId
+        :param query_counter: QueryCounter used for queries created without explicit counter,
+        a new in-memory counter starting from 1 is default
+        """
+        self.creator_account = to_wire(creator_account)
+        self.query_counter = query_counter if query_counter is not None else QueryCounter()
 
     @staticmethod
     def _camel_case_to_snake_case(camel_case_string):
@@ -204,7 +281,7 @@ class Iroha(object):
                     creator_account=None, created_time=None):
         """
         Creates a protobuf transaction with specified set of entities
-        :param commands: list of commands generated via command factory method
+        :param commands: list of commands generated via command factory method or CommandBuilder instances
         :param quorum: required number of signatures, 1 is default
         :param creator_account: id of transaction creator account
         :param created_time: transaction creation timestamp in milliseconds
@@ -221,21 +298,27 @@ class Iroha(object):
         # setting transaction contents
         core_payload.quorum = quorum
         core_payload.created_time = created_time
-        core_payload.creator_account_id = creator_account
-        core_payload.commands.extend(commands)
+        core_payload.creator_account_id = to_wire(creator_account)
+        core_payload.commands.extend(
+            command.to_proto() if isinstance(command, CommandBuilder) else command
+            for command in commands)
         return tx
 
     @staticmethod
     def command(name, **kwargs):
         """
         Creates a protobuf command to be inserted into a transaction
-        :param name: CamelCased name of command
-        :param kwargs: command arguments as they defined in schema
+        :param name: CamelCased name of command or a CommandBuilder instance
+        :param kwargs: command arguments as they defined in schema, ids like AccountId are accepted for string fields
         :return: a proto command
 
         Usage example:
         cmd = Iroha.command('CreateDomain', domain_id='test', default_role='user')
+        cmd = Iroha.command(commands.CreateDomain('test', 'user'))
         """
+        if isinstance(name, CommandBuilder):
+            assert not kwargs, 'Arguments are already set in the command builder'
+            return name.to_proto()
         command_wrapper = commands_pb2.Command()
         field_name = Iroha._camel_case_to_snake_case(name)
         internal_command = getattr(command_wrapper, field_name)
@@ -248,10 +331,10 @@ class Iroha(object):
                 peer_attr = getattr(internal_command, key)
                 peer_attr.CopyFrom(value)
                 continue
-            setattr(internal_command, key, value)
+            setattr(internal_command, key, to_wire(value))
         return command_wrapper
 
-    def query(self, name, counter=1, creator_account=None,
+    def query(self, name, counter=None, creator_account=None,
               created_time=None, page_size=None,
               first_tx_hash=None, first_tx_time=None,
               last_tx_time=None, first_tx_height=None,
@@ -260,7 +343,7 @@ class Iroha(object):
         """
         Creates a protobuf query with specified set of entities
         :param name: CamelCased name of query to be executed
-        :param counter: query counter, should be incremented for each new query
+        :param counter: query counter, the next value of the session query counter is default
         :param creator_account: account id of query creator
         :param created_time: query creation timestamp in milliseconds
         :param page_size: a non-zero positive number, size of result rowset for queries with pagination
@@ -271,12 +354,15 @@ class Iroha(object):
         :param last_tx_height: optional block height of last transaction
         :param ordering_sequence: an array representing an ordering spec, containing a sequence of fields and directions 
             example: [[queries_pb2.kCreatedTime, queries_pb2.kAscending],[queries_pb2.kPosition, queries_pb2.kDescending]]
-        :param kwargs: query arguments as they defined in schema
+        :param kwargs: query arguments as they defined in schema,
+            message fields like pagination_meta of GetAccountAssets can be passed as proto messages
         :return: a proto query
         """
         assert creator_account or self.creator_account, \
             "No account name specified as query creator id"
         pagination_meta = None
+        if counter is None:
+            counter = self.query_counter.next()
         if not created_time:
             created_time = self.now()
         if not creator_account:
@@ -304,7 +390,7 @@ class Iroha(object):
 
         meta = queries_pb2.QueryPayloadMeta()
         meta.created_time = created_time
-        meta.creator_account_id = creator_account
+        meta.creator_account_id = to_wire(creator_account)
         meta.query_counter = counter
 
         query_wrapper = queries_pb2.Query()
@@ -316,7 +402,11 @@ class Iroha(object):
                 hashes_attr = getattr(internal_query, key)
                 hashes_attr.extend(value)
                 continue
-            setattr(internal_query, key, value)
+            if isinstance(value, protobuf_message.Message):
+                message_attr = getattr(internal_query, key)
+                message_attr.CopyFrom(value)
+                continue
+            setattr(internal_query, key, to_wire(value))
         if not len(kwargs):
             message = getattr(queries_pb2, name)()
             internal_query.CopyFrom(message)
@@ -325,14 +415,16 @@ class Iroha(object):
             pagination_meta_attr.CopyFrom(pagination_meta)
         return query_wrapper
 
-    def blocks_query(self, counter=1, creator_account=None, created_time=None):
+    def blocks_query(self, counter=None, creator_account=None, created_time=None):
         """
         Creates a protobuf query for a blocks stream
-        :param counter: query counter, should be incremented for each new query
+        :param counter: query counter, the next value of the session query counter is default
         :param creator_account: account id of query creator
         :param created_time: query creation timestamp in milliseconds
         :return: a proto blocks query
         """
+        if counter is None:
+            counter = self.query_counter.next()
         if not created_time:
             created_time = self.now()
         if not creator_account:
@@ -340,13 +432,35 @@ class Iroha(object):
 
         meta = queries_pb2.QueryPayloadMeta()
         meta.created_time = created_time
-        meta.creator_account_id = creator_account
+        meta.creator_account_id = to_wire(creator_account)
         meta.query_counter = counter
 
         query_wrapper = queries_pb2.BlocksQuery()
         query_wrapper.meta.CopyFrom(meta)
         return query_wrapper
 
+    @staticmethod
+    def refresh_query(query, counter=None, created_time=None, query_counter=None):
+        """
+        Creates a copy of a query or a blocks query with updated meta,
+        so it can be signed and sent once again
+        :param query: proto Query or BlocksQuery
+        :param counter: new query counter, the next value of query_counter is default
+        :param created_time: new creation timestamp in milliseconds, current time is default
+        :param query_counter: QueryCounter of the session, e.g. Iroha.query_counter,
+        without it the previous counter incremented by one is used
+        :return: an unsigned copy of the query
+        """
+        if counter is None and query_counter is not None:
+            counter = query_counter.next()
+        refreshed = type(query)()
+        refreshed.CopyFrom(query)
+        refreshed.ClearField('signature')
+        meta = refreshed.payload.meta if hasattr(refreshed, 'payload') else refreshed.meta
+        meta.query_counter = counter if counter is not None else meta.query_counter + 1
+        meta.created_time = created_time if created_time else Iroha.now()
+        return refreshed
+
     @staticmethod
     def batch(transactions, atomic=True):
         """
@@ -368,18 +482,95 @@ class Iroha(object):
             transaction.payload.batch.CopyFrom(meta)
 
 
+class TxResult(object):
+    """
+    Outcome of a transaction processing collected from its status stream
+    """
+
+    def __init__(self, tx_hash, statuses=None):
+        """
+        :param tx_hash: hex string with hash of the transaction
+        :param statuses: list of protobuf ToriiResponse in the order they were received
+        """
+        self.tx_hash = tx_hash
+        self.statuses = list(statuses) if statuses else []
+
+    def __repr__(self):
+        return 'TxResult(tx_hash={!r}, history={!r})'.format(self.tx_hash, self.history)
+
+    def append(self, response):
+        """
+        Record a status received for the transaction, consecutive duplicates are skipped
+        :param response: protobuf ToriiResponse
+        """
+        if self.statuses and self.statuses[-1].tx_status == response.tx_status:
+            self.statuses[-1] = response
+            return
+        self.statuses.append(response)
+
+    @property
+    def history(self):
+        """List of symbolic names of all the statuses received"""
+        return [endpoint_pb2.TxStatus.Name(status.tx_status) for status in self.statuses]
+
+    @property
+    def status(self):
+        """Symbolic name of the last status or None if nothing was received"""
+        if not self.statuses:
+            return None
+        return endpoint_pb2.TxStatus.Name(self.statuses[-1].tx_status)
+
+    @property
+    def status_code(self):
+        return self.statuses[-1].tx_status if self.statuses else None
+
+    @property
+    def error_code(self):
+        return self.statuses[-1].error_code if self.statuses else 0
+
+    @property
+    def err_or_cmd_name(self):
+        """Name of the failed command or error description, empty if no error occurred"""
+        return self.statuses[-1].err_or_cmd_name if self.statuses else ''
+
+    @property
+    def failed_cmd_index(self):
+        """Index of the failed command within the transaction, meaningful for stateful failures"""
+        return self.statuses[-1].failed_cmd_index if self.statuses else 0
+
+    @property
+    def is_final(self):
+        return self.status in TERMINAL_TX_STATUSES
+
+    @property
+    def is_committed(self):
+        return self.status == 'COMMITTED'
+
+    def raise_for_status(self):
+        """
+        :raise: TransactionRejectedError subclass if the transaction has reached
+        a terminal status other than COMMITTED
+        """
+        error_type = TX_STATUS_ERRORS.get(self.status)
+        if error_type:
+            raise error_type(self)
+
+
 class IrohaGrpc(object):
     """
     Possible implementation of gRPC transport to Iroha
     """
 
-    def __init__(self, address=None, timeout=None, secure=False, root_certificates=None, private_key=None, certificate_chain=None, *, max_message_length=None):
+    def __init__(self, address=None, timeout=None, secure=False, root_certificates=None, private_key=None, certificate_chain=None, *, max_message_length=None, retry_policy=None, query_counter=None):
         """
         Create Iroha gRPC client
         :param address: Iroha Torii address with port, example "127.0.0.1:50051"
         :param timeout: timeout for network I/O operations in seconds
         :param secure: enable grpc ssl channel
         :param max_message_length: it is max message length in bytes for grpc
+        :param retry_policy: optional RetryPolicy applied to send_tx, send_txs and send_query
+        :param query_counter: optional QueryCounter of the session queries are re-signed with on retries,
+        e.g. Iroha.query_counter
         :param root_certificates The PEM-encoded root certificates as a byte string,
         or None to retrieve them from a default location chosen by gRPC
         runtime. https://grpc.io/docs/guides/auth/
@@ -390,12 +581,7 @@ class IrohaGrpc(object):
         """
         self._address = address if address else '127.0.0.1:50051'
 
-        channel_kwargs = {}
-        if max_message_length is not None:
-            channel_kwargs['options'] = [
-                ('grpc.max_send_message_length', max_message_length),
-                ('grpc.max_receive_message_length', max_message_length)]
-
+        channel_kwargs = self._channel_kwargs(max_message_length)
         if secure:
             self._channel = grpc.secure_channel(self._address, grpc.ssl_channel_credentials(
                 root_certificates, private_key, certificate_chain), **channel_kwargs)
@@ -403,14 +589,32 @@ class IrohaGrpc(object):
             self._channel = grpc.insecure_channel(self._address, **channel_kwargs)
 
         self._timeout = timeout
+        self._retry_policy = retry_policy
+        self._query_counter = query_counter
         self._command_service_stub = endpoint_pb2_grpc.CommandService_v1Stub(
             self._channel)
         self._query_service_stub = endpoint_pb2_grpc.QueryService_v1Stub(
             self._channel)
 
+    @staticmethod
+    def _channel_kwargs(max_message_length=None):
+        """
+        Prepare keyword arguments for gRPC channel creation
+        :param max_message_length: max message length in bytes for grpc, or None to keep gRPC defaults
+        :return: a dict to be passed to a channel factory
+        """
+        channel_kwargs = {}
+        if max_message_length is not None:
+            channel_kwargs['options'] = [
+                ('grpc.max_send_message_length', max_message_length),
+                ('grpc.max_receive_message_length', max_message_length)]
+        return channel_kwargs
+
     def send_tx(self, transaction, timeout=None):
         """
-        Send a transaction to Iroha
+        Send a transaction to Iroha.
+        With a retry policy the same signed transaction is resent on transient errors,
+        but only if Iroha reports NOT_RECEIVED status for its hash.
         :param transaction: protobuf Transaction
         :param timeout: timeout for network I/O operations in seconds
         :return: None
@@ -418,12 +622,23 @@ class IrohaGrpc(object):
         """
         if not timeout:
             timeout = self._timeout
-        self._command_service_stub.Torii(transaction, timeout=timeout)
+        if not self._retry_policy:
+            self._command_service_stub.Torii(transaction, timeout=timeout)
+            return
+
+        def attempt(number):
+            if number > 1 and not self._not_received([transaction], timeout):
+                return
+            self._command_service_stub.Torii(transaction, timeout=timeout)
+
+        self._retry_policy.run(attempt)
 
     def send_txs(self, transactions, timeout=None):
         """
         Send a series of transactions to Iroha at once.
         Useful for submitting batches of transactions.
+        With a retry policy only transactions in NOT_RECEIVED status are resent on transient errors,
+        batches are always resent as a whole.
         :param transactions: list of protobuf transactions to be sent
         :param timeout: timeout for network I/O operations in seconds
         :return: None
@@ -431,23 +646,87 @@ class IrohaGrpc(object):
         """
         if not timeout:
             timeout = self._timeout
-        tx_list = endpoint_pb2.TxList()
-        tx_list.transactions.extend(transactions)
-        self._command_service_stub.ListTorii(tx_list, timeout=timeout)
 
-    def send_query(self, query, timeout=None):
-        """
-        Send a query to Iroha
+        def attempt(number):
+            pending = transactions
+            if number > 1:
+                pending = self._not_received(transactions, timeout)
+                if not pending:
+                    return
+                if any(tx.payload.HasField('batch') for tx in pending):
+                    pending = transactions
+            tx_list = endpoint_pb2.TxList()
+            tx_list.transactions.extend(pending)
+            self._command_service_stub.ListTorii(tx_list, timeout=timeout)
+
+        if not self._retry_policy:
+            attempt(1)
+            return
+        self._retry_policy.run(attempt)
+
+    def send_query(self, query, timeout=None, private_key=None, unwrap=False):
+        """
+        Send a query to Iroha.
+        With a retry policy the query is retried on transient errors only if
+        the private key is passed, each retry is re-signed with a fresh query counter
+        taken from the session query counter if the client has one.
         :param query: protobuf Query
         :param timeout: timeout for network I/O operations in seconds
+        :param private_key: optional key to re-sign the query on retries
+        :param unwrap: return the inner response message instead of QueryResponse
+        and raise QueryError subclass if Iroha answered with ErrorResponse
         :return: a protobuf response to the query
-        :raise: grpc.RpcError with .code() available in case of any error
+        :raise: grpc.RpcError with .code() available in case of any error,
+        QueryError subclass on ErrorResponse when unwrap is requested
         """
         if not timeout:
             timeout = self._timeout
-        response = self._query_service_stub.Find(query, timeout=timeout)
+        if not self._retry_policy or private_key is None:
+            response = self._query_service_stub.Find(query, timeout=timeout)
+        else:
+            def attempt(number):
+                nonlocal query
+                if number > 1:
+                    query = Iroha.refresh_query(query, query_counter=self._query_counter)
+                    IrohaCrypto.sign_query(query, private_key)
+                return self._query_service_stub.Find(query, timeout=timeout)
+
+            response = self._retry_policy.run(attempt)
+        if unwrap:
+            return self.unwrap_query_response(response)
         return response
 
+    @staticmethod
+    def unwrap_query_response(response):
+        """
+        Extract the actual response from protobuf QueryResponse
+        :param response: protobuf QueryResponse
+        :return: the message set in QueryResponse.response, e.g. AccountResponse
+        :raise: QueryError subclass matching ErrorResponse.Reason if Iroha answered with an error,
+        UnexpectedQueryResponseError if the response is empty
+        """
+        field_name = response.WhichOneof('response')
+        if field_name == 'error_response':
+            raise QueryError.from_error_response(response.error_response)
+        if field_name is None:
+            raise UnexpectedQueryResponseError('Empty query response')
+        return getattr(response, field_name)
+
+    def _not_received(self, transactions, timeout):
+        """
+        Select transactions unknown to Iroha
+        :param transactions: list of protobuf transactions
+        :param timeout: timeout for network I/O operations in seconds
+        :return: list of transactions with NOT_RECEIVED status
+        """
+        not_received = []
+        for transaction in transactions:
+            request = self._tx_status_request(IrohaCrypto.hash(transaction))
+            response = self._command_service_stub.Status(request, timeout=timeout)
+            if response.tx_status == endpoint_pb2.NOT_RECEIVED:
+                not_received.append(transaction)
+        return not_received
+
     def send_blocks_stream_query(self, query, timeout=None):
         """
         Send a query for blocks stream to Iroha
@@ -463,6 +742,62 @@ class IrohaGrpc(object):
         for block in response:
             yield block
 
+    def healthcheck(self, timeout=None):
+        """
+        Request health information of the connected peer
+        :param timeout: timeout for network I/O operations in seconds
+        :return: a protobuf HealthcheckData with memory_consumption, is_healthy,
+        is_syncing, last_block_height and last_block_reject fields
+        :raise: grpc.RpcError with .code() available in case of any error
+        """
+        if not timeout:
+            timeout = self._timeout
+        response = self._query_service_stub.Healthcheck(
+            empty_pb2.Empty(), timeout=timeout)
+        return response
+
+    @staticmethod
+    def is_ready(healthcheck_data, min_height=None):
+        """
+        Check whether a peer described by healthcheck data is able to serve clients
+        :param healthcheck_data: protobuf HealthcheckData
+        :param min_height: optional block height the peer has to reach
+        :return: bool, whether the peer is healthy, not syncing and at or above min_height
+        """
+        if not healthcheck_data.HasField('is_healthy') or not healthcheck_data.is_healthy:
+            return False
+        if healthcheck_data.HasField('is_syncing') and healthcheck_data.is_syncing:
+            return False
+        if min_height is not None and healthcheck_data.last_block_height < min_height:
+            return False
+        return True
+
+    def wait_until_ready(self, min_height=None, timeout=None, poll_interval=1.0):
+        """
+        Block until the peer is healthy, not syncing and reached the given height.
+        Connection errors are treated as "not ready yet" while the peer is starting up.
+        :param min_height: optional block height the peer has to reach
+        :param timeout: overall time limit in seconds, None means wait forever
+        :param poll_interval: delay between healthcheck requests in seconds
+        :return: the last protobuf HealthcheckData received
+        :raise: TimeoutError if the peer has not become ready in time,
+        grpc.RpcError with .code() available in case of any non-transient error
+        """
+        deadline = time.monotonic() + timeout if timeout is not None else None
+        while True:
+            try:
+                data = self.healthcheck()
+                if self.is_ready(data, min_height):
+                    return data
+            except grpc.RpcError as rpc_error:
+                if rpc_error.code() not in (grpc.StatusCode.UNAVAILABLE,
+                                            grpc.StatusCode.DEADLINE_EXCEEDED):
+                    raise
+            if deadline is not None and time.monotonic() + poll_interval > deadline:
+                raise TimeoutError(
+                    'Peer {} is not ready after {} seconds'.format(self._address, timeout))
+            time.sleep(poll_interval)
+
     def tx_status(self, transaction, timeout=None):
         """
         Request a status of a transaction
@@ -474,8 +809,7 @@ class IrohaGrpc(object):
         """
         if not timeout:
             timeout = self._timeout
-        request = endpoint_pb2.TxStatusRequest()
-        request.tx_hash = binascii.hexlify(IrohaCrypto.hash(transaction))
+        request = self._tx_status_request(IrohaCrypto.hash(transaction))
         response = self._command_service_stub.Status(request, timeout=timeout)
         return self._parse_tx_status(response)
 
@@ -502,11 +836,7 @@ class IrohaGrpc(object):
         """
         if not timeout:
             timeout = self._timeout
-        request = endpoint_pb2.TxStatusRequest()
-        if isinstance(transaction_hash, bytes):
-            request.tx_hash = binascii.hexlify(transaction_hash)
-        else:
-            request.tx_hash = transaction_hash.encode('utf-8')
+        request = self._tx_status_request(transaction_hash)
         response = self._command_service_stub.StatusStream(
             request, timeout=timeout)
         for status in response:
@@ -514,6 +844,109 @@ class IrohaGrpc(object):
                 status)
             yield status_name, status_code, error_code
 
+    def send_tx_await(self, transaction, timeout=None, raise_on_reject=True):
+        """
+        Send a transaction to Iroha and follow its status stream until a terminal status
+        :param transaction: protobuf Transaction
+        :param timeout: overall time limit in seconds, None means wait forever
+        :param raise_on_reject: raise an exception if the transaction is not committed
+        :return: TxResult with the full status history
+        :raise: TransactionRejectedError subclass on rejection,
+        TransactionTimeoutError if no terminal status is reached in time,
+        grpc.RpcError with .code() available in case of any network error
+        """
+        deadline = time.monotonic() + timeout if timeout is not None else None
+        self.send_tx(transaction, timeout=self._remaining(deadline))
+        return self.tx_hash_await(IrohaCrypto.hash(transaction), timeout, raise_on_reject,
+                                  _deadline=deadline)
+
+    def send_txs_await(self, transactions, timeout=None, raise_on_reject=True):
+        """
+        Send a series of transactions (e.g. a batch) to Iroha at once
+        and follow status streams of all of them until terminal statuses
+        :param transactions: list of protobuf transactions to be sent
+        :param timeout: overall time limit in seconds, None means wait forever
+        :param raise_on_reject: raise an exception if any of transactions is not committed
+        :return: list of TxResult in the order of transactions
+        :raise: TransactionRejectedError subclass for the first not committed transaction,
+        TransactionTimeoutError if no terminal status is reached in time,
+        grpc.RpcError with .code() available in case of any network error
+        """
+        deadline = time.monotonic() + timeout if timeout is not None else None
+        self.send_txs(transactions, timeout=self._remaining(deadline))
+        results = [self.tx_hash_await(IrohaCrypto.hash(transaction), timeout, False,
+                                      _deadline=deadline)
+                   for transaction in transactions]
+        if raise_on_reject:
+            for result in results:
+                result.raise_for_status()
+        return results
+
+    def tx_hash_await(self, transaction_hash: "str or bytes", timeout=None, raise_on_reject=True,
+                      poll_interval=1.0, *, _deadline=None):
+        """
+        Follow status stream of a transaction until a terminal status.
+        The stream is reopened if Iroha closes it before the transaction is finalized.
+        :param transaction_hash: the hash of transaction, which status is about to be known
+        :param timeout: overall time limit in seconds, None means wait forever
+        :param raise_on_reject: raise an exception if the transaction is not committed
+        :param poll_interval: delay in seconds before reopening a prematurely closed stream
+        :return: TxResult with the full status history
+        :raise: TransactionRejectedError subclass on rejection,
+        TransactionTimeoutError if no terminal status is reached in time,
+        grpc.RpcError with .code() available in case of any network error
+        """
+        if _deadline is None and timeout is not None:
+            _deadline = time.monotonic() + timeout
+        request = self._tx_status_request(transaction_hash)
+        result = TxResult(request.tx_hash)
+        while not result.is_final:
+            try:
+                stream = self._command_service_stub.StatusStream(
+                    request, timeout=self._remaining(_deadline))
+                for status in stream:
+                    result.append(status)
+                    if result.is_final:
+                        break
+            except grpc.RpcError as rpc_error:
+                if rpc_error.code() != grpc.StatusCode.DEADLINE_EXCEEDED:
+                    raise
+            if result.is_final:
+                break
+            if _deadline is not None and time.monotonic() + poll_interval >= _deadline:
+                raise TransactionTimeoutError(result, timeout)
+            time.sleep(poll_interval)
+        if raise_on_reject:
+            result.raise_for_status()
+        return result
+
+    def _remaining(self, deadline):
+        """
+        Time left for a network call
+        :param deadline: time.monotonic() based deadline or None
+        :return: timeout in seconds to be passed to a stub
+        """
+        if deadline is None:
+            return self._timeout
+        remaining = max(deadline - time.monotonic(), 0)
+        if self._timeout:
+            return min(remaining, self._timeout)
+        return remaining
+
+    @staticmethod
+    def _tx_status_request(transaction_hash: "str or bytes"):
+        """
+        Create protocol.TxStatusRequest for a transaction hash
+        :param transaction_hash: raw bytes of hash or its hex representation
+        :return: a proto TxStatusRequest
+        """
+        request = endpoint_pb2.TxStatusRequest()
+        if isinstance(transaction_hash, bytes):
+            request.tx_hash = binascii.hexlify(transaction_hash)
+        else:
+            request.tx_hash = transaction_hash.encode('utf-8')
+        return request
+
     @staticmethod
     def _parse_tx_status(response):
         """
@@ -0,0 +1,593 @@
+#!/usr/bin/env python3
+#
+# Copyright Soramitsu Co., Ltd. All Rights Reserved.
+# SPDX-License-Identifier: Apache-2.0
+#
+
+import json
+
+from . import block_pb2
+from . import primitive_pb2
+from . import qry_responses_pb2
+from . import transaction_pb2
+from .amount import Amount
+from .commands import CommandBuilder
+from .iroha import IrohaCrypto, IrohaGrpc
+
+
+def _parse_json(json_data):
+    return json.loads(json_data) if json_data else {}
+
+
+def _dump_json(value, original):
+    """Keep the original text when the parsed value has not been changed"""
+    if original is not None and _parse_json(original) == value:
+        return original
+    return json.dumps(value, separators=(',', ':'))
+
+
+def _optional(message, field):
+    return getattr(message, field) if message.HasField(field) else None
+
+
+class Model(object):
+    """
+    Base class of Python representations of protobuf messages.
+    from_proto() and to_proto() convert in both directions without losing data.
+    """
+
+    # public attributes in schema order
+    _fields = ()
+    # protobuf message class the model represents
+    _proto_type = None
+
+    @classmethod
+    def from_proto(cls, message):
+        """
+        :param message: protobuf message of the _proto_type
+        :return: an instance of the model
+        """
+        raise NotImplementedError
+
+    def to_proto(self):
+        """
+        :return: a new protobuf message equal to the one the model was created from
+        """
+        message = self._proto_type()
+        self._fill(message)
+        return message
+
+    def _fill(self, message):
+        raise NotImplementedError
+
+    def __eq__(self, other):
+        return type(self) is type(other) and all(
+            getattr(self, field) == getattr(other, field) for field in self._fields)
+
+    def __ne__(self, other):
+        return not self == other
+
+    def __repr__(self):
+        return '{}({})'.format(type(self).__name__, ', '.join(
+            '{}={!r}'.format(field, getattr(self, field)) for field in self._fields))
+
+
+class Asset(Model):
+    _fields = ('asset_id', 'domain_id', 'precision')
+    _proto_type = qry_responses_pb2.Asset
+
+    def __init__(self, asset_id, domain_id, precision):
+        self.asset_id = asset_id
+        self.domain_id = domain_id
+        self.precision = precision
+
+    @classmethod
+    def from_proto(cls, message):
+        return cls(message.asset_id, message.domain_id, message.precision)
+
+    def _fill(self, message):
+        message.asset_id = self.asset_id
+        message.domain_id = self.domain_id
+        message.precision = self.precision
+
+
+class Account(Model):
+    """
+    Account with its details parsed into a {writer: {key: value}} dict
+    """
+
+    _fields = ('account_id', 'domain_id', 'quorum', 'details')
+    _proto_type = qry_responses_pb2.Account
+
+    def __init__(self, account_id, domain_id, quorum, details=None, json_data=None):
+        """
+        :param details: {writer: {key: value}} dict
+        :param json_data: original json_data text, kept while details are not modified
+        """
+        self.account_id = account_id
+        self.domain_id = domain_id
+        self.quorum = quorum
+        self.details = _parse_json(json_data) if details is None else details
+        self._json_data = json_data
+
+    @classmethod
+    def from_proto(cls, message):
+        return cls(message.account_id, message.domain_id, message.quorum, json_data=message.json_data)
+
+    def _fill(self, message):
+        message.account_id = self.account_id
+        message.domain_id = self.domain_id
+        message.quorum = self.quorum
+        message.json_data = _dump_json(self.details, self._json_data)
+
+
+class AccountAsset(Model):
+    """
+    Balance of an account, balance is an Amount
+    """
+
+    _fields = ('asset_id', 'account_id', 'balance')
+    _proto_type = qry_responses_pb2.AccountAsset
+
+    def __init__(self, asset_id, account_id, balance):
+        self.asset_id = asset_id
+        self.account_id = account_id
+        self.balance = Amount(balance)
+
+    @classmethod
+    def from_proto(cls, message):
+        return cls(message.asset_id, message.account_id, message.balance)
+
+    def _fill(self, message):
+        message.asset_id = self.asset_id
+        message.account_id = self.account_id
+        message.balance = str(self.balance)
+
+
+class Peer(Model):
+    _fields = ('address', 'peer_key', 'tls_certificate', 'syncing_peer')
+    _proto_type = primitive_pb2.Peer
+
+    def __init__(self, address, peer_key, tls_certificate=None, syncing_peer=False):
+        self.address = address
+        self.peer_key = peer_key
+        self.tls_certificate = tls_certificate
+        self.syncing_peer = syncing_peer
+
+    @classmethod
+    def from_proto(cls, message):
+        return cls(message.address, message.peer_key,
+                   _optional(message, 'tls_certificate'), message.syncing_peer)
+
+    def _fill(self, message):
+        message.address = self.address
+        message.peer_key = self.peer_key
+        if self.tls_certificate is not None:
+            message.tls_certificate = self.tls_certificate
+        message.syncing_peer = self.syncing_peer
+
+
+class EngineLog(Model):
+    _fields = ('address', 'data', 'topics')
+    _proto_type = primitive_pb2.EngineLog
+
+    def __init__(self, address, data, topics=()):
+        self.address = address
+        self.data = data
+        self.topics = list(topics)
+
+    @classmethod
+    def from_proto(cls, message):
+        return cls(message.address, message.data, message.topics)
+
+    def _fill(self, message):
+        message.address = self.address
+        message.data = self.data
+        message.topics.extend(self.topics)
+
+
+class EngineReceipt(Model):
+    """
+    Result of a CallEngine command, either call_result (callee, result_data) or contract_address is set
+    """
+
+    _fields = ('command_index', 'caller', 'call_result', 'contract_address', 'logs')
+    _proto_type = primitive_pb2.EngineReceipt
+
+    def __init__(self, command_index, caller, call_result=None, contract_address=None, logs=()):
+        self.command_index = command_index
+        self.caller = caller
+        self.call_result = call_result
+        self.contract_address = contract_address
+        self.logs = list(logs)
+
+    @classmethod
+    def from_proto(cls, message):
+        call_result = None
+        if message.HasField('call_result'):
+            call_result = (message.call_result.callee, message.call_result.result_data)
+        return cls(message.command_index, message.caller, call_result,
+                   _optional(message, 'contract_address'),
+                   [EngineLog.from_proto(log) for log in message.logs])
+
+    def _fill(self, message):
+        message.command_index = self.command_index
+        message.caller = self.caller
+        if self.call_result is not None:
+            message.call_result.callee, message.call_result.result_data = self.call_result
+        if self.contract_address is not None:
+            message.contract_address = self.contract_address
+        message.logs.extend(log.to_proto() for log in self.logs)
+
+
+class Transaction(Model):
+    """
+    Transaction with decoded commands, see iroha.commands for the builder types.
+    batch is None or a (batch type name, reduced hashes) tuple,
+    signatures is a list of (public key, signature) tuples.
+    """
+
+    _fields = ('creator_account_id', 'created_time', 'quorum', 'commands', 'batch', 'signatures')
+    _proto_type = transaction_pb2.Transaction
+
+    def __init__(self, creator_account_id, created_time, quorum, commands, batch=None, signatures=()):
+        self.creator_account_id = creator_account_id
+        self.created_time = created_time
+        self.quorum = quorum
+        self.commands = list(commands)
+        self.batch = batch
+        self.signatures = list(signatures)
+
+    @classmethod
+    def from_proto(cls, message):
+        payload = message.payload
+        batch = None
+        if payload.HasField('batch'):
+            batch = (transaction_pb2.Transaction.Payload.BatchMeta.BatchType.Name(payload.batch.type),
+                     list(payload.batch.reduced_hashes))
+        return cls(payload.reduced_payload.creator_account_id,
+                   payload.reduced_payload.created_time,
+                   payload.reduced_payload.quorum,
+                   [CommandBuilder.from_proto(command) for command in payload.reduced_payload.commands],
+                   batch,
+                   [(signature.public_key, signature.signature) for signature in message.signatures])
+
+    def _fill(self, message):
+        reduced_payload = message.payload.reduced_payload
+        reduced_payload.commands.extend(command.to_proto() for command in self.commands)
+        reduced_payload.creator_account_id = self.creator_account_id
+        reduced_payload.created_time = self.created_time
+        reduced_payload.quorum = self.quorum
+        if self.batch is not None:
+            batch_type, reduced_hashes = self.batch
+            message.payload.batch.type = \
+                transaction_pb2.Transaction.Payload.BatchMeta.BatchType.Value(batch_type)
+            message.payload.batch.reduced_hashes.extend(reduced_hashes)
+        for public_key, signature in self.signatures:
+            message.signatures.add(public_key=public_key, signature=signature)
+
+    @property
+    def hash(self):
+        """Hex hash of the transaction as used in status requests"""
+        return IrohaCrypto.hash(self.to_proto()).hex()
+
+
+class Response(Model):
+    """
+    Base class of query response variants, query_hash is kept for the conversion back to QueryResponse
+    """
+
+    # name of the field in protobuf QueryResponse
+    _response_field = None
+
+    def to_query_response(self, query_hash=None):
+        """
+        :param query_hash: hash of the answered query, the parsed one by default
+        :return: protobuf QueryResponse
+        """
+        response = qry_responses_pb2.QueryResponse()
+        getattr(response, self._response_field).CopyFrom(self.to_proto())
+        response.query_hash = getattr(self, 'query_hash', '') if query_hash is None else query_hash
+        return response
+
+
+class AccountAssetResponse(Response):
+    """
+    Page of account balances, next_asset_id is None on the last page
+    """
+
+    _fields = ('account_assets', 'total_number', 'next_asset_id')
+    _proto_type = qry_responses_pb2.AccountAssetResponse
+    _response_field = 'account_assets_response'
+
+    def __init__(self, account_assets, total_number, next_asset_id=None):
+        self.account_assets = list(account_assets)
+        self.total_number = total_number
+        self.next_asset_id = next_asset_id
+
+    @classmethod
+    def from_proto(cls, message):
+        return cls([AccountAsset.from_proto(asset) for asset in message.account_assets],
+                   message.total_number, _optional(message, 'next_asset_id'))
+
+    def _fill(self, message):
+        message.account_assets.extend(asset.to_proto() for asset in self.account_assets)
+        message.total_number = self.total_number
+        if self.next_asset_id is not None:
+            message.next_asset_id = self.next_asset_id
+
+
+class AccountDetailResponse(Response):
+    """
+    Page of account details parsed into a {writer: {key: value}} dict,
+    next_record_id is None on the last page or a (writer, key) tuple
+    """
+
+    _fields = ('details', 'total_number', 'next_record_id')
+    _proto_type = qry_responses_pb2.AccountDetailResponse
+    _response_field = 'account_detail_response'
+
+    def __init__(self, details, total_number=0, next_record_id=None, json_data=None):
+        self.details = details
+        self.total_number = total_number
+        self.next_record_id = next_record_id
+        self._json_data = json_data
+
+    @classmethod
+    def from_proto(cls, message):
+        next_record_id = None
+        if message.HasField('next_record_id'):
+            next_record_id = (message.next_record_id.writer, message.next_record_id.key)
+        return cls(_parse_json(message.detail), message.total_number, next_record_id, message.detail)
+
+    def _fill(self, message):
+        message.detail = _dump_json(self.details, self._json_data)
+        message.total_number = self.total_number
+        if self.next_record_id is not None:
+            message.next_record_id.writer, message.next_record_id.key = self.next_record_id
+
+
+class AccountResponse(Response):
+    _fields = ('account', 'roles')
+    _proto_type = qry_responses_pb2.AccountResponse
+    _response_field = 'account_response'
+
+    def __init__(self, account, roles=()):
+        self.account = account
+        self.roles = list(roles)
+
+    @classmethod
+    def from_proto(cls, message):
+        return cls(Account.from_proto(message.account), message.account_roles)
+
+    def _fill(self, message):
+        message.account.CopyFrom(self.account.to_proto())
+        message.account_roles.extend(self.roles)
+
+
+class AssetResponse(Response):
+    _fields = ('asset',)
+    _proto_type = qry_responses_pb2.AssetResponse
+    _response_field = 'asset_response'
+
+    def __init__(self, asset):
+        self.asset = asset
+
+    @classmethod
+    def from_proto(cls, message):
+        return cls(Asset.from_proto(message.asset))
+
+    def _fill(self, message):
+        message.asset.CopyFrom(self.asset.to_proto())
+
+
+class RolesResponse(Response):
+    _fields = ('roles',)
+    _proto_type = qry_responses_pb2.RolesResponse
+    _response_field = 'roles_response'
+
+    def __init__(self, roles):
+        self.roles = list(roles)
+
+    @classmethod
+    def from_proto(cls, message):
+        return cls(message.roles)
+
+    def _fill(self, message):
+        message.roles.extend(self.roles)
+
+
+class RolePermissionsResponse(Response):
+    """
+    Permissions of a role as RolePermission names like 'can_transfer'
+    """
+
+    _fields = ('permissions',)
+    _proto_type = qry_responses_pb2.RolePermissionsResponse
+    _response_field = 'role_permissions_response'
+
+    def __init__(self, permissions):
+        self.permissions = list(permissions)
+
+    @classmethod
+    def from_proto(cls, message):
+        return cls([primitive_pb2.RolePermission.Name(permission) for permission in message.permissions])
+
+    def _fill(self, message):
+        message.permissions.extend(
+            primitive_pb2.RolePermission.Value(permission) for permission in self.permissions)
+
+
+class SignatoriesResponse(Response):
+    _fields = ('keys',)
+    _proto_type = qry_responses_pb2.SignatoriesResponse
+    _response_field = 'signatories_response'
+
+    def __init__(self, keys):
+        self.keys = list(keys)
+
+    @classmethod
+    def from_proto(cls, message):
+        return cls(message.keys)
+
+    def _fill(self, message):
+        message.keys.extend(self.keys)
+
+
+class TransactionsResponse(Response):
+    _fields = ('transactions',)
+    _proto_type = qry_responses_pb2.TransactionsResponse
+    _response_field = 'transactions_response'
+
+    def __init__(self, transactions):
+        self.transactions = list(transactions)
+
+    @classmethod
+    def from_proto(cls, message):
+        return cls([Transaction.from_proto(tx) for tx in message.transactions])
+
+    def _fill(self, message):
+        message.transactions.extend(tx.to_proto() for tx in self.transactions)
+
+
+class TransactionsPageResponse(Response):
+    """
+    Page of transactions, next_tx_hash is None on the last page
+    """
+
+    _fields = ('transactions', 'all_transactions_size', 'next_tx_hash')
+    _proto_type = qry_responses_pb2.TransactionsPageResponse
+    _response_field = 'transactions_page_response'
+
+    def __init__(self, transactions, all_transactions_size, next_tx_hash=None):
+        self.transactions = list(transactions)
+        self.all_transactions_size = all_transactions_size
+        self.next_tx_hash = next_tx_hash
+
+    @classmethod
+    def from_proto(cls, message):
+        return cls([Transaction.from_proto(tx) for tx in message.transactions],
+                   message.all_transactions_size, _optional(message, 'next_tx_hash'))
+
+    def _fill(self, message):
+        message.transactions.extend(tx.to_proto() for tx in self.transactions)
+        message.all_transactions_size = self.all_transactions_size
+        if self.next_tx_hash is not None:
+            message.next_tx_hash = self.next_tx_hash
+
+
+class PendingTransactionsPageResponse(Response):
+    """
+    Page of pending transactions,
+    next_batch_info is None on the last page or a (first tx hash, batch size) tuple
+    """
+
+    _fields = ('transactions', 'all_transactions_size', 'next_batch_info')
+    _proto_type = qry_responses_pb2.PendingTransactionsPageResponse
+    _response_field = 'pending_transactions_page_response'
+
+    def __init__(self, transactions, all_transactions_size, next_batch_info=None):
+        self.transactions = list(transactions)
+        self.all_transactions_size = all_transactions_size
+        self.next_batch_info = next_batch_info
+
+    @classmethod
+    def from_proto(cls, message):
+        next_batch_info = None
+        if message.HasField('next_batch_info'):
+            next_batch_info = (message.next_batch_info.first_tx_hash, message.next_batch_info.batch_size)
+        return cls([Transaction.from_proto(tx) for tx in message.transactions],
+                   message.all_transactions_size, next_batch_info)
+
+    def _fill(self, message):
+        message.transactions.extend(tx.to_proto() for tx in self.transactions)
+        message.all_transactions_size = self.all_transactions_size
+        if self.next_batch_info is not None:
+            message.next_batch_info.first_tx_hash, message.next_batch_info.batch_size = self.next_batch_info
+
+
+class BlockResponse(Response):
+    """
+    Block is kept as protobuf Block, transactions of the block are available decoded
+    """
+
+    _fields = ('block',)
+    _proto_type = qry_responses_pb2.BlockResponse
+    _response_field = 'block_response'
+
+    def __init__(self, block):
+        self.block = block
+
+    @classmethod
+    def from_proto(cls, message):
+        block = block_pb2.Block()
+        block.CopyFrom(message.block)
+        return cls(block)
+
+    def _fill(self, message):
+        message.block.CopyFrom(self.block)
+
+    @property
+    def height(self):
+        return self.block.block_v1.payload.height
+
+    @property
+    def transactions(self):
+        """
+        :return: list of Transaction models of the block
+        """
+        return [Transaction.from_proto(tx) for tx in self.block.block_v1.payload.transactions]
+
+
+class PeersResponse(Response):
+    _fields = ('peers',)
+    _proto_type = qry_responses_pb2.PeersResponse
+    _response_field = 'peers_response'
+
+    def __init__(self, peers):
+        self.peers = list(peers)
+
+    @classmethod
+    def from_proto(cls, message):
+        return cls([Peer.from_proto(peer) for peer in message.peers])
+
+    def _fill(self, message):
+        message.peers.extend(peer.to_proto() for peer in self.peers)
+
+
+class EngineReceiptsResponse(Response):
+    _fields = ('engine_receipts',)
+    _proto_type = qry_responses_pb2.EngineReceiptsResponse
+    _response_field = 'engine_receipts_response'
+
+    def __init__(self, engine_receipts):
+        self.engine_receipts = list(engine_receipts)
+
+    @classmethod
+    def from_proto(cls, message):
+        return cls([EngineReceipt.from_proto(receipt) for receipt in message.engine_receipts])
+
+    def _fill(self, message):
+        message.engine_receipts.extend(receipt.to_proto() for receipt in self.engine_receipts)
+
+
+RESPONSES = (
+    AccountAssetResponse, AccountDetailResponse, AccountResponse, AssetResponse, RolesResponse,
+    RolePermissionsResponse, SignatoriesResponse, TransactionsResponse, TransactionsPageResponse,
+    PendingTransactionsPageResponse, BlockResponse, PeersResponse, EngineReceiptsResponse,
+)
+RESPONSES_BY_FIELD = {response._response_field: response for response in RESPONSES}
+
+
+def from_query_response(response):
+    """
+    Convert a protobuf QueryResponse into a model of its variant
+    :param response: protobuf QueryResponse
+    :return: an instance of the matching Response subclass with query_hash set
+    :raise: QueryError subclass if Iroha answered with ErrorResponse
+    """
+    IrohaGrpc.unwrap_query_response(response)
+    field = response.WhichOneof('response')
+    model = RESPONSES_BY_FIELD[field].from_proto(getattr(response, field))
+    model.query_hash = response.query_hash
+    return model
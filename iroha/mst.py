@@ -0,0 +1,255 @@
+#!/usr/bin/env python3
+#
+# Copyright Soramitsu Co., Ltd. All Rights Reserved.
+# SPDX-License-Identifier: Apache-2.0
+#
+
+import collections
+import time
+
+from . import endpoint_pb2
+from . import transaction_pb2
+from .errors import QueryError, TransactionTimeoutError
+from .iroha import IrohaCrypto, TxResult
+from .pagination import IrohaPaginator
+
+MST_PENDING = 'MST_PENDING'
+ENOUGH_SIGNATURES_COLLECTED = 'ENOUGH_SIGNATURES_COLLECTED'
+MST_EXPIRED = 'MST_EXPIRED'
+
+
+def _hex(value):
+    return (value.decode() if isinstance(value, bytes) else value).lower()
+
+
+def mst_state(status_name):
+    """
+    Reduce a transaction status to the multi-signature stage it shows
+    :param status_name: symbolic status name, e.g. 'STATEFUL_VALIDATION_SUCCESS'
+    :return: MST_PENDING, ENOUGH_SIGNATURES_COLLECTED, MST_EXPIRED
+    or None if the transaction is unknown or failed before signatures were counted.
+    STATELESS_VALIDATION_SUCCESS is reported as MST_PENDING since signatures are not counted yet.
+    """
+    if status_name in (MST_PENDING, MST_EXPIRED):
+        return status_name
+    if status_name == 'STATELESS_VALIDATION_SUCCESS':
+        return MST_PENDING
+    if status_name in ('NOT_RECEIVED', 'STATELESS_VALIDATION_FAILED'):
+        return None
+    return ENOUGH_SIGNATURES_COLLECTED
+
+
+class PendingTransaction(object):
+    """
+    Pending multi-signature transaction with the state of its signatures
+    """
+
+    def __init__(self, transaction, signatories=None):
+        """
+        :param transaction: protobuf Transaction as returned by GetPendingTransactions
+        :param signatories: public keys of the creator account, None if unknown
+        """
+        self.transaction = transaction
+        self.tx_hash = IrohaCrypto.hash(transaction).hex()
+        self.creator_account_id = transaction.payload.reduced_payload.creator_account_id
+        self.quorum = transaction.payload.reduced_payload.quorum
+        self.signed_keys = [_hex(signature.public_key) for signature in transaction.signatures]
+        self.signatories = None if signatories is None else [_hex(key) for key in signatories]
+
+    @property
+    def missing_count(self):
+        """Number of signatures left to reach the quorum of the transaction"""
+        return max(0, self.quorum - len(self.signed_keys))
+
+    @property
+    def missing_keys(self):
+        """Signatories of the creator that have not signed yet, None if signatories are unknown"""
+        if self.signatories is None:
+            return None
+        return [key for key in self.signatories if key not in self.signed_keys]
+
+    def is_signed_by(self, public_key):
+        return _hex(public_key) in self.signed_keys
+
+    def awaits(self, public_key):
+        """
+        :param public_key: hex public key
+        :return: whether a signature of the key is still needed, a key outside
+        of the known signatories of the creator is never awaited
+        """
+        if not self.missing_count or self.is_signed_by(public_key):
+            return False
+        return self.signatories is None or _hex(public_key) in self.signatories
+
+    def __repr__(self):
+        return 'PendingTransaction(tx_hash={!r}, creator_account_id={!r}, signed={}/{})'.format(
+            self.tx_hash, self.creator_account_id, len(self.signed_keys), self.quorum)
+
+
+class PendingBatch(object):
+    """
+    Pending transactions tied into one batch, members created by other accounts may be absent
+    """
+
+    def __init__(self, transactions):
+        """
+        :param transactions: list of PendingTransaction with the same batch meta
+        """
+        self.transactions = transactions
+        meta = transactions[0].transaction.payload.batch
+        self.is_atomic = meta.type == transaction_pb2.Transaction.Payload.BatchMeta.ATOMIC
+        self.reduced_hashes = list(meta.reduced_hashes)
+
+    @property
+    def is_complete(self):
+        """Whether all the members of the batch are in the pending list"""
+        return len(self.transactions) == len(self.reduced_hashes)
+
+    @property
+    def missing_count(self):
+        return sum(pending.missing_count for pending in self.transactions)
+
+    def awaits(self, public_key):
+        return any(pending.awaits(public_key) for pending in self.transactions)
+
+    def __repr__(self):
+        return 'PendingBatch({} of {} transactions, {} signatures missing)'.format(
+            len(self.transactions), len(self.reduced_hashes), self.missing_count)
+
+
+class MstWorkflow(object):
+    """
+    Co-signing of pending multi-signature transactions of an account:
+    list what is pending, add signatures, resubmit and track MST states.
+    """
+
+    def __init__(self, iroha, net, private_key, page_size=100, timeout=None):
+        """
+        :param iroha: Iroha instance with the creator account whose pending transactions are handled
+        :param net: IrohaGrpc (or compatible) transport
+        :param private_key: key of a signatory of the account, signs queries and co-signs transactions
+        :param page_size: number of transactions requested per page
+        :param timeout: timeout for network I/O operations in seconds
+        """
+        self._iroha = iroha
+        self._net = net
+        self._private_key = private_key
+        self._timeout = timeout
+        self._paginator = IrohaPaginator(iroha, net, private_key, page_size, timeout)
+        self.public_key = _hex(IrohaCrypto.derive_public_key(private_key))
+
+    def signatories(self, account_id):
+        """
+        :param account_id: account id
+        :return: list of public keys of the account or None if they cannot be queried
+        """
+        query = IrohaCrypto.sign_query(self._iroha.query('GetSignatories', account_id=account_id),
+                                       self._private_key)
+        try:
+            return list(self._net.send_query(query, self._timeout, unwrap=True).keys)
+        except QueryError:
+            return None
+
+    def pending(self, with_signatories=True):
+        """
+        List pending transactions, members of a batch are grouped together
+        :param with_signatories: query signatories of creators to report missing keys
+        :return: list of PendingTransaction and PendingBatch in the order Iroha returned them
+        """
+        signatories = {}
+        items = []
+        batches = {}
+        for tx in self._paginator.pending_transactions():
+            creator = tx.payload.reduced_payload.creator_account_id
+            if with_signatories and creator not in signatories:
+                signatories[creator] = self.signatories(creator)
+            pending = PendingTransaction(tx, signatories.get(creator))
+            if not tx.payload.HasField('batch'):
+                items.append(pending)
+                continue
+            key = tuple(tx.payload.batch.reduced_hashes)
+            if key not in batches:
+                batches[key] = []
+                items.append(key)
+            batches[key].append(pending)
+        return [PendingBatch(batches[item]) if isinstance(item, tuple) else item for item in items]
+
+    def awaiting(self, public_key=None, with_signatories=True):
+        """
+        :param public_key: hex public key, the key of the workflow by default
+        :param with_signatories: query signatories of creators to skip keys that cannot sign
+        :return: pending transactions and batches that still need a signature of the key
+        """
+        public_key = public_key or self.public_key
+        return [item for item in self.pending(with_signatories) if item.awaits(public_key)]
+
+    def sign(self, item, *private_keys):
+        """
+        Create copies of pending transactions carrying only the new signatures,
+        Iroha merges them with the collected ones. Keys that have already signed are skipped.
+        :param item: PendingTransaction or PendingBatch
+        :param private_keys: hex private keys, the key of the workflow by default
+        :return: list of protobuf transactions ready to be resubmitted,
+        single transactions without new signatures are left out
+        """
+        private_keys = private_keys or (self._private_key,)
+        members = item.transactions if isinstance(item, PendingBatch) else [item]
+        signed = []
+        for pending in members:
+            tx = transaction_pb2.Transaction()
+            tx.CopyFrom(pending.transaction)
+            del tx.signatures[:]
+            keys = [key for key in private_keys
+                    if not pending.is_signed_by(IrohaCrypto.derive_public_key(key))]
+            if keys:
+                IrohaCrypto.sign_transaction(tx, *keys)
+            elif not tx.payload.HasField('batch'):
+                continue
+            signed.append(tx)
+        return signed
+
+    def co_sign(self, item, *private_keys):
+        """
+        Sign a pending transaction or batch and resubmit it
+        :param item: PendingTransaction or PendingBatch
+        :param private_keys: hex private keys, the key of the workflow by default
+        :return: hex hashes of the resubmitted transactions
+        :raise: ValueError if some members of the batch are not pending,
+        grpc.RpcError with .code() available in case of any error
+        """
+        if isinstance(item, PendingBatch) and not item.is_complete:
+            raise ValueError('Batch is incomplete: {} of {} transactions are pending'.format(
+                len(item.transactions), len(item.reduced_hashes)))
+        transactions = self.sign(item, *private_keys)
+        self._net.send_txs(transactions, self._timeout)
+        return [IrohaCrypto.hash(tx).hex() for tx in transactions]
+
+    def states(self, item):
+        """
+        :param item: PendingTransaction or PendingBatch
+        :return: dict of transaction hash to its MST state, see mst_state
+        """
+        members = item.transactions if isinstance(item, PendingBatch) else [item]
+        return collections.OrderedDict(
+            (pending.tx_hash, mst_state(self._net.tx_status(pending.transaction, self._timeout)[0]))
+            for pending in members)
+
+    def wait(self, item, timeout=None, poll_interval=1.0):
+        """
+        Poll statuses until no transaction of the item is in MST_PENDING state
+        :param item: PendingTransaction or PendingBatch
+        :param timeout: overall time limit in seconds, None means wait forever
+        :param poll_interval: delay in seconds between status requests
+        :return: dict of transaction hash to its MST state, see mst_state
+        :raise: TransactionTimeoutError if signatures are still pending in time
+        """
+        deadline = time.monotonic() + timeout if timeout is not None else None
+        while True:
+            states = self.states(item)
+            pending = [tx_hash for tx_hash, state in states.items() if state == MST_PENDING]
+            if not pending:
+                return states
+            if deadline is not None and time.monotonic() + poll_interval >= deadline:
+                status = endpoint_pb2.ToriiResponse(tx_status=endpoint_pb2.MST_PENDING, tx_hash=pending[0])
+                raise TransactionTimeoutError(TxResult(pending[0], [status]), timeout)
+            time.sleep(poll_interval)
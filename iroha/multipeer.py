@@ -0,0 +1,377 @@
+#!/usr/bin/env python3
+#
+# Copyright Soramitsu Co., Ltd. All Rights Reserved.
+# SPDX-License-Identifier: Apache-2.0
+#
+
+import concurrent.futures
+import threading
+import time
+
+import grpc
+
+from . import qry_responses_pb2
+from .errors import BlockStreamError
+from .iroha import Iroha, IrohaCrypto, IrohaGrpc
+
+FAILOVER_STATUS_CODES = (grpc.StatusCode.UNAVAILABLE,
+                         grpc.StatusCode.DEADLINE_EXCEEDED)
+
+
+class PeerState(object):
+    """
+    Client-side knowledge about a single peer of the network
+    """
+
+    def __init__(self, address, client):
+        self.address = address
+        self.client = client
+        self.latency = None
+        self.last_block_height = None
+        self.failed_until = 0.0
+
+    def __repr__(self):
+        return 'PeerState(address={!r}, latency={!r}, last_block_height={!r})'.format(
+            self.address, self.latency, self.last_block_height)
+
+    @property
+    def is_available(self):
+        return time.monotonic() >= self.failed_until
+
+    def record_success(self, duration, smoothing=0.3):
+        """
+        Update exponentially weighted moving average of call latency
+        :param duration: duration of the last successful call in seconds
+        :param smoothing: weight of the last measurement
+        """
+        if self.latency is None:
+            self.latency = duration
+        else:
+            self.latency = smoothing * duration + (1 - smoothing) * self.latency
+        self.failed_until = 0.0
+
+    def record_failure(self, cooldown):
+        """
+        Exclude the peer from preferred candidates for a while
+        :param cooldown: period in seconds
+        """
+        self.failed_until = time.monotonic() + cooldown
+
+
+class RoundRobinPolicy(object):
+    """
+    Spread calls evenly across peers
+    """
+
+    def __init__(self):
+        self._lock = threading.Lock()
+        self._next = 0
+
+    def order(self, peers):
+        """
+        :param peers: list of PeerState
+        :return: list of PeerState in the order they should be tried
+        """
+        with self._lock:
+            start = self._next % len(peers)
+            self._next += 1
+        return peers[start:] + peers[:start]
+
+
+class LowestLatencyPolicy(object):
+    """
+    Prefer peers with the lowest observed call latency.
+    Peers without measurements yet are tried first to get them measured.
+    """
+
+    def order(self, peers):
+        """
+        :param peers: list of PeerState
+        :return: list of PeerState in the order they should be tried
+        """
+        return sorted(peers, key=lambda peer: (peer.latency is not None, peer.latency or 0))
+
+
+class HighestHeightPolicy(object):
+    """
+    Prefer peers with the highest last_block_height reported by Healthcheck
+    """
+
+    def __init__(self, refresh_interval=5.0, timeout=1.0):
+        """
+        :param refresh_interval: how often peers heights are requested, in seconds
+        :param timeout: timeout for each Healthcheck call in seconds
+        """
+        self._refresh_interval = refresh_interval
+        self._timeout = timeout
+        self._refreshed_at = None
+        self._lock = threading.Lock()
+
+    def order(self, peers):
+        """
+        :param peers: list of PeerState
+        :return: list of PeerState in the order they should be tried
+        """
+        with self._lock:
+            now = time.monotonic()
+            due = self._refreshed_at is None or now - self._refreshed_at >= self._refresh_interval
+            if due:
+                self._refreshed_at = now
+        if due:
+            with concurrent.futures.ThreadPoolExecutor(max_workers=len(peers)) as executor:
+                list(executor.map(self._refresh, peers))
+        return sorted(peers, key=lambda peer: -1 if peer.last_block_height is None
+                      else peer.last_block_height, reverse=True)
+
+    def _refresh(self, peer):
+        try:
+            data = peer.client.healthcheck(timeout=self._timeout)
+        except grpc.RpcError:
+            peer.last_block_height = None
+            return
+        if data.HasField('last_block_height'):
+            peer.last_block_height = data.last_block_height
+        else:
+            peer.last_block_height = None
+
+
+class IrohaMultiPeerGrpc(object):
+    """
+    gRPC transport to a set of Iroha peers with failover.
+    Calls are routed according to a selection policy and are passed over
+    to the next peer when the current one is UNAVAILABLE or the deadline is exceeded.
+    """
+
+    def __init__(self, addresses, policy=None, timeout=None, private_key=None, cooldown=5.0, query_counter=None,
+                 **grpc_kwargs):
+        """
+        Create Iroha multi-peer gRPC client
+        :param addresses: list of Iroha Torii addresses with ports, example ["127.0.0.1:50051", "127.0.0.1:50052"]
+        :param policy: peer selection policy, RoundRobinPolicy is default
+        :param timeout: timeout for network I/O operations in seconds
+        :param private_key: optional key to re-sign blocks queries and
+        to request missed blocks when a blocks stream is resumed on another peer
+        :param cooldown: period in seconds a failed peer is tried only as a last resort
+        :param query_counter: optional QueryCounter of the session, e.g. Iroha.query_counter,
+        re-signed queries and GetBlock queries for missed blocks take counters from it
+        :param grpc_kwargs: secure, root_certificates, private_key (as tls_private_key),
+        certificate_chain and max_message_length options passed to each IrohaGrpc
+        """
+        assert len(addresses), 'At least one peer address has to be passed'
+        if 'tls_private_key' in grpc_kwargs:
+            grpc_kwargs['private_key'] = grpc_kwargs.pop('tls_private_key')
+        self._peers = [PeerState(address, IrohaGrpc(address, timeout, query_counter=query_counter, **grpc_kwargs))
+                       for address in addresses]
+        self._policy = policy if policy else RoundRobinPolicy()
+        self._timeout = timeout
+        self._private_key = private_key
+        self._cooldown = cooldown
+        self._query_counter = query_counter
+
+    @property
+    def peers(self):
+        return list(self._peers)
+
+    def _ordered_peers(self):
+        ordered = self._policy.order(self._peers)
+        available = [peer for peer in ordered if peer.is_available]
+        return available + [peer for peer in ordered if not peer.is_available]
+
+    def _call(self, method_name, *args, **kwargs):
+        return self._call_peer(method_name, *args, **kwargs)[1]
+
+    def _call_peer(self, method_name, *args, **kwargs):
+        """
+        Make a call on the first peer that answers
+        :return: tuple of the PeerState and the result of the call
+        """
+        last_error = None
+        for peer in self._ordered_peers():
+            started = time.monotonic()
+            try:
+                result = getattr(peer.client, method_name)(*args, **kwargs)
+            except grpc.RpcError as rpc_error:
+                if rpc_error.code() not in FAILOVER_STATUS_CODES:
+                    raise
+                peer.record_failure(self._cooldown)
+                last_error = rpc_error
+                continue
+            peer.record_success(time.monotonic() - started)
+            return peer, result
+        raise last_error
+
+    def _stream(self, method_name, *args, **kwargs):
+        last_error = None
+        for peer in self._ordered_peers():
+            try:
+                yield from getattr(peer.client, method_name)(*args, **kwargs)
+                return
+            except grpc.RpcError as rpc_error:
+                if rpc_error.code() not in FAILOVER_STATUS_CODES:
+                    raise
+                peer.record_failure(self._cooldown)
+                last_error = rpc_error
+        raise last_error
+
+    def send_tx(self, transaction, timeout=None):
+        """
+        Send a transaction to one of Iroha peers
+        :param transaction: protobuf Transaction
+        :param timeout: timeout for network I/O operations in seconds
+        :return: None
+        :raise: grpc.RpcError with .code() available if all the peers failed
+        """
+        self._call('send_tx', transaction, timeout)
+
+    def send_txs(self, transactions, timeout=None):
+        """
+        Send a series of transactions to one of Iroha peers at once
+        :param transactions: list of protobuf transactions to be sent
+        :param timeout: timeout for network I/O operations in seconds
+        :return: None
+        :raise: grpc.RpcError with .code() available if all the peers failed
+        """
+        self._call('send_txs', transactions, timeout)
+
+    def send_query(self, query, timeout=None, private_key=None, unwrap=False):
+        """
+        Send a query to one of Iroha peers, see IrohaGrpc.send_query
+        :param query: protobuf Query
+        :param timeout: timeout for network I/O operations in seconds
+        :param private_key: optional key to re-sign the query on retries
+        :param unwrap: return the inner response message and raise QueryError on ErrorResponse
+        :return: a protobuf response to the query
+        :raise: grpc.RpcError with .code() available if all the peers failed
+        """
+        return self._call('send_query', query, timeout, private_key, unwrap)
+
+    def healthcheck(self, timeout=None):
+        """
+        Request health information of one of Iroha peers
+        :param timeout: timeout for network I/O operations in seconds
+        :return: a protobuf HealthcheckData
+        :raise: grpc.RpcError with .code() available if all the peers failed
+        """
+        return self._call('healthcheck', timeout)
+
+    def tx_status(self, transaction, timeout=None):
+        """
+        Request a status of a transaction from one of Iroha peers
+        :param transaction: the transaction, which status is about to be known
+        :param timeout: timeout for network I/O operations in seconds
+        :return: a tuple with the symbolic status description,
+        integral status code, and error code (will be 0 if no error occurred)
+        :raise: grpc.RpcError with .code() available if all the peers failed
+        """
+        return self._call('tx_status', transaction, timeout)
+
+    def tx_status_stream(self, transaction, timeout=None):
+        """
+        Generator of transaction statuses from status stream.
+        If a peer fails the stream is reopened on another one, so some statuses may repeat.
+        :param transaction: the transaction, which status is about to be known
+        :param timeout: timeout for network I/O operations in seconds
+        :return: an iterable over a series of tuples with symbolic status description,
+        integral status code, and error code (will be 0 if no error occurred)
+        :raise: grpc.RpcError with .code() available if all the peers failed
+        """
+        yield from self.tx_hash_status_stream(IrohaCrypto.hash(transaction), timeout)
+
+    def tx_hash_status_stream(self, transaction_hash: "str or bytes", timeout=None):
+        """
+        Generator of transaction statuses from status stream.
+        If a peer fails the stream is reopened on another one, so some statuses may repeat.
+        :param transaction_hash: the hash of transaction, which status is about to be known
+        :param timeout: timeout for network I/O operations in seconds
+        :return: an iterable over a series of tuples with symbolic status description,
+        integral status code, and error code (will be 0 if no error occurred)
+        :raise: grpc.RpcError with .code() available if all the peers failed
+        """
+        yield from self._stream('tx_hash_status_stream', transaction_hash, timeout)
+
+    def send_tx_await(self, transaction, timeout=None, raise_on_reject=True):
+        """
+        Send a transaction and follow its status until a terminal one, see IrohaGrpc.send_tx_await.
+        The status is followed on the peer that accepted the transaction.
+        :param transaction: protobuf Transaction
+        :param timeout: overall time limit in seconds, None means wait forever
+        :param raise_on_reject: raise an exception if the transaction is not committed
+        :return: TxResult with the full status history
+        """
+        deadline = time.monotonic() + timeout if timeout is not None else None
+        peer, _ = self._call_peer('send_tx', transaction, timeout)
+        return peer.client.tx_hash_await(IrohaCrypto.hash(transaction), timeout, raise_on_reject,
+                                         _deadline=deadline)
+
+    def send_blocks_stream_query(self, query, timeout=None):
+        """
+        Send a query for blocks stream to Iroha peers.
+        When a peer fails the stream is resumed on another one: blocks already
+        delivered are skipped, and if a private key was passed to the client
+        the missed ones are requested with GetBlock queries.
+        The stream fails once all the peers have failed in a row.
+        :param query: protobuf BlocksQuery
+        :param timeout: timeout for network I/O operations in seconds
+        :return: an iterable over a stream of blocks
+        :raise: grpc.RpcError with .code() available if all the peers failed,
+        BlockStreamError if missed blocks cannot be requested
+        """
+        last_height = None
+        last_error = None
+        failed_peers = set()
+        while True:
+            candidates = [peer for peer in self._ordered_peers()
+                          if peer.address not in failed_peers]
+            if not candidates:
+                raise last_error
+            peer = candidates[0]
+            if last_height is not None and self._private_key:
+                query = Iroha.refresh_query(query, query_counter=self._query_counter)
+                IrohaCrypto.sign_query(query, self._private_key)
+            try:
+                for response in peer.client.send_blocks_stream_query(query, timeout):
+                    if not response.HasField('block_response'):
+                        yield response
+                        continue
+                    height = response.block_response.block.block_v1.payload.height
+                    if last_height is not None:
+                        if height <= last_height:
+                            continue
+                        for missed in self._missed_blocks(peer, query, last_height + 1, height, timeout):
+                            yield missed
+                    last_height = height
+                    failed_peers.clear()
+                    last_error = None
+                    yield response
+                return
+            except grpc.RpcError as rpc_error:
+                if rpc_error.code() not in FAILOVER_STATUS_CODES:
+                    raise
+                peer.record_failure(self._cooldown)
+                failed_peers.add(peer.address)
+                last_error = rpc_error
+
+    def _missed_blocks(self, peer, blocks_query, first_height, end_height, timeout):
+        """
+        Request blocks from first_height up to, but not including, end_height.
+        Query counters are taken from the session counter if the client has one,
+        otherwise they follow the counter of the blocks query.
+        :return: an iterable over protobuf BlockQueryResponse
+        :raise: BlockStreamError if the client has no private key or a block cannot be received
+        """
+        if first_height < end_height and not self._private_key:
+            raise BlockStreamError('Blocks {} to {} have been missed, a private key is required to request them'.format(
+                first_height, end_height - 1))
+        creator = Iroha(blocks_query.meta.creator_account_id)
+        counter = blocks_query.meta.query_counter
+        for height in range(first_height, end_height):
+            counter = self._query_counter.next() if self._query_counter else counter + 1
+            query = creator.query('GetBlock', counter=counter, height=height)
+            IrohaCrypto.sign_query(query, self._private_key)
+            response = peer.client.send_query(query, timeout)
+            if not response.HasField('block_response'):
+                raise BlockStreamError('Block {} is not available: {}'.format(
+                    height, response.error_response.message))
+            block_query_response = qry_responses_pb2.BlockQueryResponse()
+            block_query_response.block_response.CopyFrom(response.block_response)
+            yield block_query_response
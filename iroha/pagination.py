@@ -0,0 +1,197 @@
+#!/usr/bin/env python3
+#
+# Copyright Soramitsu Co., Ltd. All Rights Reserved.
+# SPDX-License-Identifier: Apache-2.0
+#
+
+import datetime
+import json
+
+from google.protobuf import timestamp_pb2
+
+from . import queries_pb2
+from .errors import UnexpectedQueryResponseError
+from .iroha import IrohaCrypto, IrohaGrpc
+
+
+class IrohaPaginator(object):
+    """
+    Generators over paged query results.
+    Each page is requested with a newly created and signed query,
+    next page tokens are followed until the last page.
+    """
+
+    def __init__(self, iroha, net, private_key, page_size=100, timeout=None):
+        """
+        :param iroha: Iroha instance used to create queries
+        :param net: IrohaGrpc (or compatible) transport used to send queries
+        :param private_key: key to sign queries with
+        :param page_size: default number of items requested per page
+        :param timeout: timeout for network I/O operations in seconds
+        """
+        self._iroha = iroha
+        self._net = net
+        self._private_key = private_key
+        self._page_size = page_size
+        self._timeout = timeout
+
+    def account_transactions(self, account_id, page_size=None, ordering_sequence=None,
+                             first_tx_time=None, last_tx_time=None,
+                             first_tx_height=None, last_tx_height=None):
+        """
+        Iterate over transactions of an account with GetAccountTransactions
+        :param account_id: id of the account
+        :param page_size: number of transactions requested per page
+        :param ordering_sequence: ordering spec, see Iroha.query
+        :param first_tx_time: optional lower time bound as datetime, milliseconds or proto Timestamp
+        :param last_tx_time: optional upper time bound as datetime, milliseconds or proto Timestamp
+        :param first_tx_height: optional lower block height bound
+        :param last_tx_height: optional upper block height bound
+        :return: an iterable over protobuf transactions
+        :raise: QueryError subclass if Iroha answered with ErrorResponse
+        """
+        yield from self._transactions_pages(
+            'GetAccountTransactions', page_size, ordering_sequence,
+            first_tx_time, last_tx_time, first_tx_height, last_tx_height,
+            account_id=account_id)
+
+    def account_asset_transactions(self, account_id, asset_id, page_size=None, ordering_sequence=None,
+                                   first_tx_time=None, last_tx_time=None,
+                                   first_tx_height=None, last_tx_height=None):
+        """
+        Iterate over transactions of an account involving an asset with GetAccountAssetTransactions
+        :param account_id: id of the account
+        :param asset_id: id of the asset
+        :param page_size: number of transactions requested per page
+        :param ordering_sequence: ordering spec, see Iroha.query
+        :param first_tx_time: optional lower time bound as datetime, milliseconds or proto Timestamp
+        :param last_tx_time: optional upper time bound as datetime, milliseconds or proto Timestamp
+        :param first_tx_height: optional lower block height bound
+        :param last_tx_height: optional upper block height bound
+        :return: an iterable over protobuf transactions
+        """
+        yield from self._transactions_pages(
+            'GetAccountAssetTransactions', page_size, ordering_sequence,
+            first_tx_time, last_tx_time, first_tx_height, last_tx_height,
+            account_id=account_id, asset_id=asset_id)
+
+    def pending_transactions(self, page_size=None, ordering_sequence=None,
+                             first_tx_time=None, last_tx_time=None,
+                             first_tx_height=None, last_tx_height=None):
+        """
+        Iterate over pending multi-signature transactions of the query creator with GetPendingTransactions.
+        Batches are never split between pages, so page size grows if a batch does not fit into it.
+        :param page_size: number of transactions requested per page
+        :param ordering_sequence: ordering spec, see Iroha.query
+        :param first_tx_time: optional lower time bound as datetime, milliseconds or proto Timestamp
+        :param last_tx_time: optional upper time bound as datetime, milliseconds or proto Timestamp
+        :param first_tx_height: optional lower block height bound
+        :param last_tx_height: optional upper block height bound
+        :return: an iterable over protobuf transactions
+        """
+        page_size = page_size or self._page_size
+        first_tx_hash = None
+        while True:
+            response = self._send(self._iroha.query(
+                'GetPendingTransactions', page_size=page_size, first_tx_hash=first_tx_hash,
+                ordering_sequence=ordering_sequence,
+                first_tx_time=self._timestamp(first_tx_time), last_tx_time=self._timestamp(last_tx_time),
+                first_tx_height=first_tx_height, last_tx_height=last_tx_height),
+                'pending_transactions_page_response')
+            yield from response.transactions
+            next_batch = response.next_batch_info
+            if not response.HasField('next_batch_info') or not next_batch.first_tx_hash:
+                return
+            first_tx_hash = next_batch.first_tx_hash
+            page_size = max(page_size, next_batch.batch_size)
+
+    def account_assets(self, account_id, page_size=None):
+        """
+        Iterate over assets of an account with GetAccountAssets
+        :param account_id: id of the account
+        :param page_size: number of assets requested per page
+        :return: an iterable over protobuf AccountAsset
+        """
+        pagination_meta = queries_pb2.AssetPaginationMeta()
+        pagination_meta.page_size = page_size or self._page_size
+        while True:
+            response = self._send(self._iroha.query(
+                'GetAccountAssets', account_id=account_id, pagination_meta=pagination_meta),
+                'account_assets_response')
+            yield from response.account_assets
+            if not response.HasField('next_asset_id'):
+                return
+            pagination_meta.first_asset_id = response.next_asset_id
+
+    def account_details(self, account_id=None, key=None, writer=None, page_size=None):
+        """
+        Iterate over account detail records with GetAccountDetail
+        :param account_id: optional id of the account, query creator account is default
+        :param key: optional key to filter records by
+        :param writer: optional writer account id to filter records by
+        :param page_size: number of records requested per page
+        :return: an iterable over tuples of writer, key and value
+        """
+        filters = {}
+        if account_id is not None:
+            filters['account_id'] = account_id
+        if key is not None:
+            filters['key'] = key
+        if writer is not None:
+            filters['writer'] = writer
+        pagination_meta = queries_pb2.AccountDetailPaginationMeta()
+        pagination_meta.page_size = page_size or self._page_size
+        while True:
+            response = self._send(self._iroha.query(
+                'GetAccountDetail', pagination_meta=pagination_meta, **filters),
+                'account_detail_response')
+            details = json.loads(response.detail) if response.detail else {}
+            for record_writer, records in details.items():
+                for record_key, value in records.items():
+                    yield record_writer, record_key, value
+            next_record = response.next_record_id
+            if not response.HasField('next_record_id') or not (next_record.writer or next_record.key):
+                return
+            pagination_meta.first_record_id.CopyFrom(next_record)
+
+    def _transactions_pages(self, name, page_size, ordering_sequence,
+                            first_tx_time, last_tx_time, first_tx_height, last_tx_height, **kwargs):
+        page_size = page_size or self._page_size
+        first_tx_hash = None
+        while True:
+            response = self._send(self._iroha.query(
+                name, page_size=page_size, first_tx_hash=first_tx_hash,
+                ordering_sequence=ordering_sequence,
+                first_tx_time=self._timestamp(first_tx_time), last_tx_time=self._timestamp(last_tx_time),
+                first_tx_height=first_tx_height, last_tx_height=last_tx_height, **kwargs),
+                'transactions_page_response')
+            yield from response.transactions
+            if not response.HasField('next_tx_hash'):
+                return
+            first_tx_hash = response.next_tx_hash
+
+    def _send(self, query, response_field):
+        IrohaCrypto.sign_query(query, self._private_key)
+        response = self._net.send_query(query, self._timeout)
+        inner_response = IrohaGrpc.unwrap_query_response(response)
+        received = response.WhichOneof('response')
+        if received != response_field:
+            raise UnexpectedQueryResponseError('Unexpected query response {}, {} expected'.format(
+                received, response_field))
+        return inner_response
+
+    @staticmethod
+    def _timestamp(value):
+        """
+        Convert a time bound to a protobuf Timestamp
+        :param value: None, datetime, milliseconds since epoch or protobuf Timestamp
+        :return: protobuf Timestamp or None
+        """
+        if value is None or isinstance(value, timestamp_pb2.Timestamp):
+            return value
+        timestamp = timestamp_pb2.Timestamp()
+        if isinstance(value, datetime.datetime):
+            timestamp.FromDatetime(value)
+        else:
+            timestamp.FromMilliseconds(int(value))
+        return timestamp
@@ -0,0 +1,98 @@
+#!/usr/bin/env python3
+#
+# Copyright Soramitsu Co., Ltd. All Rights Reserved.
+# SPDX-License-Identifier: Apache-2.0
+#
+
+"""
+Protobuf JSON as the Iroha daemon reads and writes it, e.g. in genesis.block:
+camelCase field names, enums as names, 64-bit integers as strings and default values omitted.
+Parsing accepts original snake_case field names as well.
+"""
+
+from google.protobuf import json_format
+
+from . import block_pb2
+from . import endpoint_pb2
+from . import queries_pb2
+from . import transaction_pb2
+
+# message types by their schema names
+MESSAGE_TYPES = {
+    'Transaction': transaction_pb2.Transaction,
+    'TxList': endpoint_pb2.TxList,
+    'Block': block_pb2.Block,
+    'Block_v1': block_pb2.Block_v1,
+    'Query': queries_pb2.Query,
+    'BlocksQuery': queries_pb2.BlocksQuery,
+}
+
+
+def _message_type(message_type):
+    if isinstance(message_type, str):
+        try:
+            return MESSAGE_TYPES[message_type]
+        except KeyError:
+            raise ValueError('Unknown message type {!r}, one of {} expected'.format(
+                message_type, ', '.join(sorted(MESSAGE_TYPES))))
+    return message_type
+
+
+def to_dict(message):
+    """
+    :param message: protobuf message, e.g. Transaction, Block or Query
+    :return: dict in the protobuf JSON mapping used by Iroha
+    """
+    return json_format.MessageToDict(message)
+
+
+def to_json(message, indent=2):
+    """
+    :param message: protobuf message, e.g. Transaction, Block or Query
+    :param indent: indentation of the JSON text, None for a single line
+    :return: JSON text in the protobuf JSON mapping used by Iroha
+    """
+    return json_format.MessageToJson(message, indent=indent)
+
+
+def from_dict(data, message_type):
+    """
+    :param data: dict in the protobuf JSON mapping
+    :param message_type: protobuf message class or its name from MESSAGE_TYPES
+    :return: protobuf message
+    :raise: google.protobuf.json_format.ParseError if the data does not match the schema
+    """
+    return json_format.ParseDict(data, _message_type(message_type)())
+
+
+def from_json(text, message_type):
+    """
+    :param text: JSON text in the protobuf JSON mapping
+    :param message_type: protobuf message class or its name from MESSAGE_TYPES
+    :return: protobuf message
+    :raise: google.protobuf.json_format.ParseError if the text does not match the schema
+    """
+    return json_format.Parse(text, _message_type(message_type)())
+
+
+def load(path, message_type=block_pb2.Block):
+    """
+    Read a protobuf JSON file, e.g. genesis.block of Iroha daemon
+    :param path: path of the file
+    :param message_type: protobuf message class or its name, Block by default
+    :return: protobuf message
+    """
+    with open(path, 'r') as json_file:
+        return from_json(json_file.read(), message_type)
+
+
+def save(path, message, indent=2):
+    """
+    Write a protobuf message as a JSON file Iroha daemon accepts
+    :param path: path of the file
+    :param message: protobuf message, e.g. genesis Block
+    :param indent: indentation of the JSON text
+    """
+    with open(path, 'w') as json_file:
+        json_file.write(to_json(message, indent))
+        json_file.write('\n')
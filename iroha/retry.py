@@ -0,0 +1,99 @@
+#!/usr/bin/env python3
+#
+# Copyright Soramitsu Co., Ltd. All Rights Reserved.
+# SPDX-License-Identifier: Apache-2.0
+#
+
+import random
+import time
+
+import grpc
+
+RETRYABLE_STATUS_CODES = (grpc.StatusCode.UNAVAILABLE,
+                          grpc.StatusCode.DEADLINE_EXCEEDED,
+                          grpc.StatusCode.RESOURCE_EXHAUSTED)
+
+
+class RetryPolicy(object):
+    """
+    Exponential backoff with jitter for transient gRPC failures
+    """
+
+    def __init__(self, max_attempts=5, initial_backoff=0.1, max_backoff=5.0, multiplier=2.0,
+                 jitter=0.2, budget=None, retryable_codes=RETRYABLE_STATUS_CODES,
+                 sleep=time.sleep, rng=None):
+        """
+        :param max_attempts: maximum number of attempts per call, including the first one
+        :param initial_backoff: delay before the first retry in seconds
+        :param max_backoff: upper limit for a delay between attempts in seconds
+        :param multiplier: factor the delay grows with after each attempt
+        :param jitter: relative random deviation of each delay, 0.2 means +-20%
+        :param budget: optional limit in seconds for the whole call including all the retries
+        :param retryable_codes: grpc.StatusCode values treated as transient
+        :param sleep: function used to wait between attempts
+        :param rng: random.Random instance used for jitter
+        """
+        assert max_attempts >= 1, 'At least one attempt has to be allowed'
+        self.max_attempts = max_attempts
+        self.initial_backoff = initial_backoff
+        self.max_backoff = max_backoff
+        self.multiplier = multiplier
+        self.jitter = jitter
+        self.budget = budget
+        self.retryable_codes = tuple(retryable_codes)
+        self._sleep = sleep
+        self._rng = rng if rng else random.Random()
+
+    def is_retryable(self, rpc_error):
+        """
+        :param rpc_error: grpc.RpcError raised by a call
+        :return: bool, whether the call may be attempted once again
+        """
+        return rpc_error.code() in self.retryable_codes
+
+    def backoff(self, attempt):
+        """
+        Delay after a failed attempt
+        :param attempt: number of the failed attempt starting from 1
+        :return: delay in seconds
+        """
+        try:
+            delay = min(self.max_backoff,
+                        self.initial_backoff * self.multiplier ** (attempt - 1))
+        except OverflowError:
+            delay = self.max_backoff
+        if self.jitter:
+            delay *= 1 + self._rng.uniform(-self.jitter, self.jitter)
+        return max(delay, 0.0)
+
+    def pause(self, attempt):
+        """
+        Wait before the next attempt
+        :param attempt: number of the failed attempt starting from 1
+        :return: the delay waited in seconds
+        """
+        delay = self.backoff(attempt)
+        self._sleep(delay)
+        return delay
+
+    def run(self, attempt_function):
+        """
+        Call the function until it succeeds or retries are exhausted
+        :param attempt_function: callable accepting the attempt number starting from 1
+        :return: the value returned by the successful attempt
+        :raise: the last grpc.RpcError if the error is not transient,
+        max_attempts is reached or the budget is spent
+        """
+        started = time.monotonic()
+        attempt = 0
+        while True:
+            attempt += 1
+            try:
+                return attempt_function(attempt)
+            except grpc.RpcError as rpc_error:
+                if not self.is_retryable(rpc_error) or attempt >= self.max_attempts:
+                    raise
+                delay = self.backoff(attempt)
+                if self.budget is not None and time.monotonic() - started + delay > self.budget:
+                    raise
+                self._sleep(delay)
@@ -0,0 +1,3 @@
+"""
+Utilities for testing applications built on top of the library without a running Iroha network
+"""
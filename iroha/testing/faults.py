@@ -0,0 +1,335 @@
+#!/usr/bin/env python3
+#
+# Copyright Soramitsu Co., Ltd. All Rights Reserved.
+# SPDX-License-Identifier: Apache-2.0
+#
+
+import binascii
+import collections
+import threading
+import time
+
+import grpc
+from google.protobuf import empty_pb2
+
+from .. import endpoint_pb2
+from .. import qry_responses_pb2
+from ..iroha import IrohaGrpc
+
+UNARY_METHODS = ('Torii', 'ListTorii', 'Status', 'Find', 'Healthcheck')
+STREAM_METHODS = ('StatusStream', 'FetchCommits')
+
+
+class InjectedRpcError(grpc.RpcError):
+    """
+    Error raised by a scripted call, mimics errors raised by gRPC stubs
+    """
+
+    def __init__(self, code, details=''):
+        super().__init__('{}: {}'.format(code.name, details))
+        self._code = code
+        self._details = details
+
+    def code(self):
+        return self._code
+
+    def details(self):
+        return self._details
+
+
+class PassThrough(object):
+    """
+    Forward the call to the connected peer
+    """
+
+    def unary(self, call, request, timeout, sleep):
+        return call(request, timeout=timeout)
+
+    def stream(self, call, request, timeout, sleep):
+        yield from call(request, timeout=timeout)
+
+
+class Fail(object):
+    """
+    Fail the call with a gRPC status code
+    """
+
+    def __init__(self, code=grpc.StatusCode.UNAVAILABLE, details='injected failure'):
+        """
+        :param code: grpc.StatusCode the call fails with
+        :param details: text returned by details() of the error
+        """
+        self.code = code
+        self.details = details
+
+    def unary(self, call, request, timeout, sleep):
+        raise InjectedRpcError(self.code, self.details)
+
+    def stream(self, call, request, timeout, sleep):
+        raise InjectedRpcError(self.code, self.details)
+        yield
+
+
+class Delay(object):
+    """
+    Delay the call, the call fails with DEADLINE_EXCEEDED if the delay is longer than its timeout
+    """
+
+    def __init__(self, seconds, then=None):
+        """
+        :param seconds: latency added before the call
+        :param then: step applied after the delay, PassThrough is default
+        """
+        self.seconds = seconds
+        self.then = then if then is not None else PassThrough()
+
+    def _wait(self, timeout, sleep):
+        if timeout is not None and self.seconds >= timeout:
+            sleep(timeout)
+            raise InjectedRpcError(grpc.StatusCode.DEADLINE_EXCEEDED, 'Deadline Exceeded')
+        sleep(self.seconds)
+        return None if timeout is None else timeout - self.seconds
+
+    def unary(self, call, request, timeout, sleep):
+        remaining = self._wait(timeout, sleep)
+        return self.then.unary(call, request, remaining, sleep)
+
+    def stream(self, call, request, timeout, sleep):
+        remaining = self._wait(timeout, sleep)
+        yield from self.then.stream(call, request, remaining, sleep)
+
+
+class Respond(object):
+    """
+    Answer the call without contacting the peer
+    """
+
+    def __init__(self, response, code=None, details='injected failure'):
+        """
+        :param response: protobuf message for unary calls,
+        list of protobuf messages for streaming calls
+        :param code: optional grpc.StatusCode a stream breaks with after the last message
+        :param details: text returned by details() of the error
+        """
+        self.response = response
+        self.code = code
+        self.details = details
+
+    def unary(self, call, request, timeout, sleep):
+        return self.response
+
+    def stream(self, call, request, timeout, sleep):
+        yield from self.response
+        if self.code is not None:
+            raise InjectedRpcError(self.code, self.details)
+
+
+class Truncate(object):
+    """
+    Forward a streaming call to the peer but cut the stream after a number of messages
+    """
+
+    def __init__(self, after, code=None, details='injected failure'):
+        """
+        :param after: number of messages delivered before the stream ends
+        :param code: optional grpc.StatusCode the stream breaks with, otherwise it just ends
+        :param details: text returned by details() of the error
+        """
+        self.after = after
+        self.code = code
+        self.details = details
+
+    def unary(self, call, request, timeout, sleep):
+        raise TypeError('Only streaming calls can be truncated')
+
+    def stream(self, call, request, timeout, sleep):
+        responses = call(request, timeout=timeout)
+        try:
+            for index, response in enumerate(responses):
+                if index >= self.after:
+                    break
+                yield response
+        finally:
+            if hasattr(responses, 'cancel'):
+                responses.cancel()
+        if self.code is not None:
+            raise InjectedRpcError(self.code, self.details)
+
+
+def _torii_response(tx_hash, status):
+    if isinstance(tx_hash, bytes):
+        tx_hash = binascii.hexlify(tx_hash).decode('utf-8')
+    return endpoint_pb2.ToriiResponse(tx_status=endpoint_pb2.TxStatus.Value(status), tx_hash=tx_hash)
+
+
+def tx_status(tx_hash, status, **details):
+    """
+    Build a Respond step for Status calls
+    :param tx_hash: hash of the transaction as bytes or hex string
+    :param status: TxStatus name, e.g. 'NOT_RECEIVED'
+    :param details: err_or_cmd_name, failed_cmd_index, error_code of the status
+    :return: Respond step with ToriiResponse
+    """
+    response = _torii_response(tx_hash, status)
+    for field_name, value in details.items():
+        setattr(response, field_name, value)
+    return Respond(response)
+
+
+def tx_statuses(tx_hash, *statuses, **details):
+    """
+    Build a Respond step for StatusStream calls
+    :param tx_hash: hash of the transaction as bytes or hex string
+    :param statuses: TxStatus names, e.g. 'MST_PENDING', 'MST_EXPIRED'
+    :param details: err_or_cmd_name, failed_cmd_index, error_code of the last status
+    :return: Respond step yielding ToriiResponse messages
+    """
+    responses = [_torii_response(tx_hash, status) for status in statuses]
+    for field_name, value in details.items():
+        setattr(responses[-1], field_name, value)
+    return Respond(responses)
+
+
+def error_response(reason, message='', error_code=0):
+    """
+    Build a Respond step for Find calls answered with ErrorResponse
+    :param reason: ErrorResponse.Reason name, e.g. 'NO_ACCOUNT'
+    :param message: text of the error
+    :param error_code: error code of the error
+    :return: Respond step with QueryResponse
+    """
+    response = qry_responses_pb2.QueryResponse()
+    response.error_response.reason = qry_responses_pb2.ErrorResponse.Reason.Value(reason)
+    response.error_response.message = message
+    response.error_response.error_code = error_code
+    return Respond(response)
+
+
+def empty_response():
+    """
+    Build a Respond step for Torii and ListTorii calls accepted without contacting the peer
+    :return: Respond step with Empty
+    """
+    return Respond(empty_pb2.Empty())
+
+
+class _Script(object):
+    """
+    Steps scripted for a single RPC method
+    """
+
+    def __init__(self):
+        self.steps = collections.deque()
+        self.default = PassThrough()
+
+
+class _FaultyStub(object):
+    """
+    Proxy of a generated gRPC stub applying scripted steps to each call
+    """
+
+    def __init__(self, stub, transport, methods):
+        self._stub = stub
+        self._transport = transport
+        for method in methods:
+            setattr(self, method, self._wrap(method))
+
+    def _wrap(self, method):
+        call = getattr(self._stub, method)
+        transport = self._transport
+
+        if method in STREAM_METHODS:
+            def streaming(request, timeout=None):
+                step = transport._next_step(method, request)
+                return step.stream(call, request, timeout, transport._sleep)
+            return streaming
+
+        def unary(request, timeout=None):
+            step = transport._next_step(method, request)
+            return step.unary(call, request, timeout, transport._sleep)
+        return unary
+
+
+class FaultInjectingGrpc(IrohaGrpc):
+    """
+    IrohaGrpc transport with calls scripted from test code.
+    Each RPC method has a queue of steps, a call takes the next step from the queue
+    of its method, calls are forwarded to the peer once the queue is empty.
+    Fully scripted tests do not need a running peer.
+    """
+
+    def __init__(self, address=None, timeout=None, *, sleep=time.sleep, **kwargs):
+        """
+        :param address: Iroha Torii address with port, calls not answered by steps are forwarded there
+        :param timeout: timeout for network I/O operations in seconds
+        :param sleep: function used to simulate latency
+        :param kwargs: other keyword arguments of IrohaGrpc
+        """
+        super().__init__(address, timeout, **kwargs)
+        self._sleep = sleep
+        self._lock = threading.Lock()
+        self._scripts = collections.defaultdict(_Script)
+        self.calls = []
+        self._command_service_stub = _FaultyStub(
+            self._command_service_stub, self, ('Torii', 'ListTorii', 'Status', 'StatusStream'))
+        self._query_service_stub = _FaultyStub(
+            self._query_service_stub, self, ('Find', 'FetchCommits', 'Healthcheck'))
+
+    def inject(self, method, *steps):
+        """
+        Append steps to the script of a method
+        :param method: name of RPC method, e.g. 'Torii', 'StatusStream', 'Find'
+        :param steps: step objects applied to the following calls one by one
+        :return: the transport itself to allow chaining
+        """
+        self._check_method(method)
+        with self._lock:
+            self._scripts[method].steps.extend(steps)
+        return self
+
+    def always(self, method, step):
+        """
+        Set the step applied to calls of a method once its script is exhausted
+        :param method: name of RPC method
+        :param step: step object, PassThrough restores forwarding to the peer
+        :return: the transport itself to allow chaining
+        """
+        self._check_method(method)
+        with self._lock:
+            self._scripts[method].default = step
+        return self
+
+    def reset(self):
+        """
+        Forget all the scripts and recorded calls
+        """
+        with self._lock:
+            self._scripts.clear()
+            self.calls = []
+
+    def pending_steps(self, method):
+        """
+        :param method: name of RPC method
+        :return: number of scripted steps not used yet
+        """
+        with self._lock:
+            return len(self._scripts[method].steps)
+
+    def call_count(self, method):
+        """
+        :param method: name of RPC method
+        :return: number of calls made to the method
+        """
+        with self._lock:
+            return sum(1 for called, _ in self.calls if called == method)
+
+    def _next_step(self, method, request):
+        with self._lock:
+            self.calls.append((method, request))
+            script = self._scripts[method]
+            return script.steps.popleft() if script.steps else script.default
+
+    @staticmethod
+    def _check_method(method):
+        if method not in UNARY_METHODS + STREAM_METHODS:
+            raise ValueError('Unknown RPC method {}'.format(method))
@@ -0,0 +1,984 @@
+#!/usr/bin/env python3
+#
+# Copyright Soramitsu Co., Ltd. All Rights Reserved.
+# SPDX-License-Identifier: Apache-2.0
+#
+
+import binascii
+import collections
+import concurrent.futures
+import copy
+import json
+import threading
+import time
+
+import grpc
+from google.protobuf import empty_pb2
+
+from .. import block_pb2
+from .. import endpoint_pb2
+from .. import endpoint_pb2_grpc
+from .. import primitive_pb2
+from .. import qry_responses_pb2
+from .. import queries_pb2
+from .. import transaction_pb2
+from ..amount import Amount
+from ..errors import ValidationError
+from ..genesis import example_genesis
+from ..iroha import Iroha, IrohaCrypto, IrohaGrpc
+from ..validation import StatelessValidator
+
+ADMIN_PRIVATE_KEY = 'f101537e319568c765b2cc89698325604991dca57b9716b58016b253506cab70'
+NODE_PRIVATE_KEY = 'cc5013e43918bd0e5c4d800416c88bed77892ff077929162bb03ead40a745e88'
+TEST_PUBLIC_KEY = '716fe505f69f18511a1b083915aa9ff73ef36e6688199f3959750db38b8f4bfc'
+
+STREAM_FINAL_STATUSES = (endpoint_pb2.COMMITTED, endpoint_pb2.REJECTED,
+                         endpoint_pb2.STATELESS_VALIDATION_FAILED, endpoint_pb2.MST_EXPIRED)
+
+
+def default_genesis_transaction(admin_public_key=None, peer_key=None, peer_address='127.0.0.1:10001'):
+    """
+    Creates a genesis transaction equal to the one from docker/iroha/genesis.block
+    :param admin_public_key: public key of admin@test, the key of the example admin is default
+    :param peer_key: public key of the peer, the key of the example node is default
+    :param peer_address: internal address of the peer
+    :return: a proto transaction
+    """
+    if admin_public_key is None:
+        admin_public_key = IrohaCrypto.derive_public_key(ADMIN_PRIVATE_KEY).decode('utf-8')
+    if peer_key is None:
+        peer_key = IrohaCrypto.derive_public_key(NODE_PRIVATE_KEY).decode('utf-8')
+    return example_genesis([(peer_address, peer_key)], admin_public_key, TEST_PUBLIC_KEY).transactions()[0]
+
+
+def _hex_hash(proto):
+    return binascii.hexlify(IrohaCrypto.hash(proto)).decode('utf-8')
+
+
+def _domain_of(identifier):
+    return identifier.rsplit('@', 1)[-1].rsplit('#', 1)[-1]
+
+
+class CommandError(Exception):
+    """
+    Command has failed stateful validation
+    """
+
+    def __init__(self, code, message=''):
+        super().__init__(message or 'error code {}'.format(code))
+        self.code = code
+
+
+class QueryFailure(Exception):
+    """
+    Query has to be answered with ErrorResponse
+    """
+
+    def __init__(self, reason, message, error_code=0):
+        super().__init__(message)
+        self.reason = reason
+        self.message = message
+        self.error_code = error_code
+
+
+class MockAccount(object):
+    """
+    Account as it is stored in the world state
+    """
+
+    def __init__(self, account_id, public_key, roles):
+        self.account_id = account_id
+        self.quorum = 1
+        self.signatories = [public_key.lower()]
+        self.roles = list(roles)
+        self.details = collections.OrderedDict()
+
+    @property
+    def domain_id(self):
+        return _domain_of(self.account_id)
+
+    def json_data(self):
+        return json.dumps(self.details)
+
+
+class WorldState(object):
+    """
+    In-memory world state view
+    """
+
+    def __init__(self):
+        self.peers = collections.OrderedDict()
+        self.roles = collections.OrderedDict()
+        self.domains = collections.OrderedDict()
+        self.assets = collections.OrderedDict()
+        self.accounts = collections.OrderedDict()
+        self.balances = collections.defaultdict(dict)
+        self.grants = set()
+        self.settings = {}
+
+    def permissions(self, account_id):
+        """
+        :param account_id: id of the account
+        :return: set of RolePermission values granted to the account by its roles
+        """
+        account = self.accounts.get(account_id)
+        if account is None:
+            return set()
+        permissions = set()
+        for role in account.roles:
+            permissions |= self.roles.get(role, set())
+        return permissions
+
+    def has_permission(self, account_id, *names):
+        """
+        :param account_id: id of the account
+        :param names: names of RolePermission values
+        :return: bool, whether the account has root or any of the permissions
+        """
+        permissions = self.permissions(account_id)
+        if primitive_pb2.root in permissions:
+            return True
+        return any(primitive_pb2.RolePermission.Value(name) in permissions for name in names)
+
+    def has_grant(self, grantor, grantee, name):
+        return (grantor, grantee, primitive_pb2.GrantablePermission.Value(name)) in self.grants
+
+    def balance(self, account_id, asset_id):
+        return self.balances[account_id].get(asset_id, Amount(0, self.assets[asset_id]))
+
+
+class MockIrohaNode(endpoint_pb2_grpc.CommandService_v1Servicer, endpoint_pb2_grpc.QueryService_v1Servicer):
+    """
+    In-process gRPC server implementing CommandService_v1 and QueryService_v1
+    against an in-memory world state. Each Torii call is processed synchronously:
+    transactions with enough signatures are validated and committed to a new block
+    right away, multi-signature transactions wait for signatures until they expire.
+    """
+
+    def __init__(self, genesis=None, node_private_key=NODE_PRIVATE_KEY, mst_expiration=24 * 60 * 60,
+                 status_stream_timeout=10.0, max_workers=10):
+        """
+        :param genesis: list of proto transactions or a proto Block to be used as the first block,
+        the transaction of docker/iroha/genesis.block is default
+        :param node_private_key: key the blocks are signed with
+        :param mst_expiration: period in seconds pending multi-signature transactions live for
+        :param status_stream_timeout: period in seconds StatusStream waits for a final status
+        :param max_workers: size of the server thread pool
+        """
+        self._lock = threading.RLock()
+        self._condition = threading.Condition(self._lock)
+        self._node_private_key = node_private_key
+        self._mst_expiration = mst_expiration
+        self._status_stream_timeout = status_stream_timeout
+        self._max_workers = max_workers
+        self._validator = StatelessValidator()
+        self._server = None
+        self._port = None
+        self.state = WorldState()
+        self.blocks = []
+        self._statuses = {}
+        self._pending = collections.OrderedDict()
+        self._committed = []
+        self._committed_hashes = set()
+
+        if genesis is None:
+            genesis = [default_genesis_transaction()]
+        elif isinstance(genesis, block_pb2.Block):
+            genesis = list(genesis.block_v1.payload.transactions)
+        for tx in genesis:
+            failure = self._apply_transaction(self.state, tx, genesis=True)
+            assert failure is None, 'Genesis transaction is invalid: {}'.format(failure)
+        self._create_block(genesis, [])
+        for tx in genesis:
+            self._set_status(_hex_hash(tx), endpoint_pb2.COMMITTED)
+
+    # *** Server lifecycle *** #
+
+    def start(self):
+        """
+        Start serving on a free localhost port
+        :return: the node itself
+        """
+        self._server = grpc.server(concurrent.futures.ThreadPoolExecutor(max_workers=self._max_workers))
+        endpoint_pb2_grpc.add_CommandService_v1Servicer_to_server(self, self._server)
+        endpoint_pb2_grpc.add_QueryService_v1Servicer_to_server(self, self._server)
+        self._port = self._server.add_insecure_port('127.0.0.1:0')
+        self._server.start()
+        return self
+
+    def stop(self, grace=None):
+        """
+        Stop serving, active streams are cancelled
+        :param grace: optional period in seconds to let active calls finish
+        """
+        if self._server is not None:
+            self._server.stop(grace).wait()
+            self._server = None
+
+    def __enter__(self):
+        return self.start()
+
+    def __exit__(self, exc_type, exc_value, traceback):
+        self.stop()
+
+    @property
+    def address(self):
+        """Torii address of the running node"""
+        assert self._port is not None, 'The node is not started'
+        return '127.0.0.1:{}'.format(self._port)
+
+    def client(self, **kwargs):
+        """
+        :param kwargs: keyword arguments of IrohaGrpc
+        :return: IrohaGrpc connected to the node
+        """
+        return IrohaGrpc(self.address, **kwargs)
+
+    @property
+    def height(self):
+        with self._lock:
+            return len(self.blocks)
+
+    # *** CommandService_v1 *** #
+
+    def Torii(self, request, context):
+        self.submit([request])
+        return empty_pb2.Empty()
+
+    def ListTorii(self, request, context):
+        self.submit(list(request.transactions))
+        return empty_pb2.Empty()
+
+    def Status(self, request, context):
+        with self._lock:
+            self._expire_pending()
+            history = self._statuses.get(request.tx_hash)
+            if history:
+                return history[-1]
+        return endpoint_pb2.ToriiResponse(tx_status=endpoint_pb2.NOT_RECEIVED, tx_hash=request.tx_hash)
+
+    def StatusStream(self, request, context):
+        deadline = time.monotonic() + self._status_stream_timeout
+        sent = 0
+        announced = False
+        while context.is_active():
+            with self._condition:
+                self._expire_pending()
+                history = list(self._statuses.get(request.tx_hash, ()))
+                if len(history) <= sent and (history or announced):
+                    if time.monotonic() >= deadline:
+                        return
+                    self._condition.wait(0.1)
+                    continue
+            if not history:
+                announced = True
+                yield endpoint_pb2.ToriiResponse(tx_status=endpoint_pb2.NOT_RECEIVED, tx_hash=request.tx_hash)
+                continue
+            for response in history[sent:]:
+                yield response
+                if response.tx_status in STREAM_FINAL_STATUSES:
+                    return
+            sent = len(history)
+
+    # *** Transactions processing *** #
+
+    def submit(self, transactions):
+        """
+        Process transactions as if they were received by Torii
+        :param transactions: list of proto transactions
+        """
+        with self._condition:
+            self._expire_pending()
+            for tx in transactions:
+                self._receive(tx)
+            ready = self._ready_transactions()
+            for tx_hash, tx in ready:
+                del self._pending[tx_hash]
+                self._set_status(tx_hash, endpoint_pb2.ENOUGH_SIGNATURES_COLLECTED)
+            for tx_hash in self._pending:
+                self._set_status(tx_hash, endpoint_pb2.MST_PENDING)
+            if ready:
+                self._commit(ready)
+            self._condition.notify_all()
+
+    def _receive(self, tx):
+        tx_hash = _hex_hash(tx)
+        if tx_hash in self._committed_hashes or self._is_final(tx_hash):
+            return
+        error = self._stateless_error(tx)
+        if error:
+            self._set_status(tx_hash, endpoint_pb2.STATELESS_VALIDATION_FAILED, err_or_cmd_name=error)
+            return
+        if tx_hash in self._pending:
+            pending_tx, received = self._pending[tx_hash]
+            known_keys = {signature.public_key for signature in pending_tx.signatures}
+            pending_tx.signatures.extend(signature for signature in tx.signatures
+                                         if signature.public_key not in known_keys)
+            return
+        self._set_status(tx_hash, endpoint_pb2.STATELESS_VALIDATION_SUCCESS)
+        stored = transaction_pb2.Transaction()
+        stored.CopyFrom(tx)
+        self._pending[tx_hash] = (stored, time.monotonic())
+
+    def _ready_transactions(self):
+        """
+        :return: list of pending (hash, transaction) with enough signatures
+        and all the members of their atomic batches available
+        """
+        signed = [(tx_hash, tx) for tx_hash, (tx, _) in self._pending.items()
+                  if len(tx.signatures) >= tx.payload.reduced_payload.quorum]
+        signed_reduced_hashes = {IrohaCrypto.reduced_hash(tx).decode('utf-8') for _, tx in signed}
+        ready = []
+        for tx_hash, tx in signed:
+            batch = tx.payload.batch if tx.payload.HasField('batch') else None
+            if batch is not None and batch.type == transaction_pb2.Transaction.Payload.BatchMeta.ATOMIC \
+                    and not set(batch.reduced_hashes) <= signed_reduced_hashes:
+                continue
+            ready.append((tx_hash, tx))
+        return ready
+
+    def _stateless_error(self, tx):
+        # transactions of batches may be submitted without signatures to be signed by other peers
+        findings = self._validator.validate_transaction(tx, require_signatures=not tx.payload.HasField('batch'))
+        if findings:
+            return '; '.join(str(finding) for finding in findings)
+        for signature in tx.signatures:
+            if not IrohaCrypto.is_any_signature_valid(tx, signature):
+                return 'Bad signature of key {}'.format(signature.public_key)
+        return None
+
+    def _expire_pending(self):
+        now = time.monotonic()
+        for tx_hash, (tx, received) in list(self._pending.items()):
+            if now - received >= self._mst_expiration:
+                del self._pending[tx_hash]
+                self._set_status(tx_hash, endpoint_pb2.MST_EXPIRED)
+
+    def _commit(self, ready):
+        state = self.state
+        accepted = []
+        rejected = []
+        for group in self._groups(ready):
+            trial = copy.deepcopy(state)
+            failure = None
+            for tx_hash, tx in group:
+                failure = self._apply_transaction(trial, tx)
+                if failure is not None:
+                    failure = (tx_hash,) + failure
+                    break
+            if failure is None:
+                state = trial
+                accepted.extend(group)
+                for tx_hash, _ in group:
+                    self._set_status(tx_hash, endpoint_pb2.STATEFUL_VALIDATION_SUCCESS)
+                continue
+            failed_hash, cmd_name, cmd_index, error_code = failure
+            for tx_hash, _ in group:
+                if tx_hash == failed_hash:
+                    details = dict(err_or_cmd_name=cmd_name, failed_cmd_index=cmd_index, error_code=error_code)
+                else:
+                    details = dict(err_or_cmd_name='Another transaction of the atomic batch has failed')
+                self._set_status(tx_hash, endpoint_pb2.STATEFUL_VALIDATION_FAILED, **details)
+                rejected.append((tx_hash, details))
+        self.state = state
+        self._create_block([tx for _, tx in accepted], [tx_hash for tx_hash, _ in rejected])
+        for tx_hash, _ in accepted:
+            self._set_status(tx_hash, endpoint_pb2.COMMITTED)
+        for tx_hash, details in rejected:
+            self._set_status(tx_hash, endpoint_pb2.REJECTED, **details)
+
+    @staticmethod
+    def _groups(ready):
+        """
+        Split ready transactions into atomic units
+        :return: list of lists of (hash, transaction)
+        """
+        groups = []
+        batches = {}
+        for tx_hash, tx in ready:
+            batch = tx.payload.batch if tx.payload.HasField('batch') else None
+            if batch is None or batch.type != transaction_pb2.Transaction.Payload.BatchMeta.ATOMIC:
+                groups.append([(tx_hash, tx)])
+                continue
+            key = tuple(batch.reduced_hashes)
+            if key not in batches:
+                batches[key] = []
+                groups.append(batches[key])
+            batches[key].append((tx_hash, tx))
+        return groups
+
+    def _create_block(self, transactions, rejected_hashes):
+        block = block_pb2.Block()
+        payload = block.block_v1.payload
+        payload.transactions.extend(transactions)
+        payload.tx_number = len(transactions)
+        payload.height = len(self.blocks) + 1
+        payload.prev_block_hash = _hex_hash(self.blocks[-1].block_v1) if self.blocks else '0' * 64
+        payload.created_time = Iroha.now()
+        payload.rejected_transactions_hashes.extend(rejected_hashes)
+        if self._node_private_key:
+            IrohaCrypto.sign_transaction(block.block_v1, self._node_private_key)
+        self.blocks.append(block)
+        for position, tx in enumerate(transactions):
+            tx_hash = _hex_hash(tx)
+            self._committed.append((payload.height, position, tx_hash, tx))
+            self._committed_hashes.add(tx_hash)
+
+    def _set_status(self, tx_hash, status, **details):
+        history = self._statuses.setdefault(tx_hash, [])
+        if history and history[-1].tx_status == status:
+            return
+        history.append(endpoint_pb2.ToriiResponse(tx_status=status, tx_hash=tx_hash, **details))
+
+    def _is_final(self, tx_hash):
+        history = self._statuses.get(tx_hash)
+        return bool(history) and history[-1].tx_status in STREAM_FINAL_STATUSES
+
+    # *** Commands *** #
+
+    def _apply_transaction(self, state, tx, genesis=False):
+        """
+        Apply commands of a transaction to the state
+        :return: None on success, otherwise a tuple of failed command name, its index and error code
+        """
+        reduced_payload = tx.payload.reduced_payload
+        creator = reduced_payload.creator_account_id
+        if not genesis:
+            account = state.accounts.get(creator)
+            keys = {signature.public_key.lower() for signature in tx.signatures}
+            if account is None or not keys <= set(account.signatories) or len(keys) < account.quorum:
+                return 'Signatures validation', 0, 1
+        for index, command in enumerate(reduced_payload.commands):
+            field_name = command.WhichOneof('command')
+            internal_command = getattr(command, field_name)
+            try:
+                getattr(self, '_execute_' + field_name)(state, creator, internal_command, genesis)
+            except CommandError as error:
+                return internal_command.DESCRIPTOR.name, index, error.code
+        return None
+
+    @staticmethod
+    def _require(state, creator, genesis, *permissions):
+        if genesis or state.has_permission(creator, *permissions):
+            return
+        raise CommandError(2, 'No such permissions')
+
+    @staticmethod
+    def _amount(state, asset_id, amount, missing_asset_code):
+        if asset_id not in state.assets:
+            raise CommandError(missing_asset_code, 'No such asset')
+        try:
+            value = Amount(amount, state.assets[asset_id])
+        except ValidationError as error:
+            raise CommandError(1, error.message)
+        if not value:
+            raise CommandError(1, 'Amount has to be positive')
+        return value
+
+    @staticmethod
+    def _add(balance, value, overflow_code, message):
+        try:
+            return balance + value
+        except ValidationError:
+            raise CommandError(overflow_code, message)
+
+    def _execute_add_asset_quantity(self, state, creator, cmd, genesis):
+        if not genesis and not state.has_permission(creator, 'can_add_asset_qty') \
+                and not (state.has_permission(creator, 'can_add_domain_asset_qty')
+                         and _domain_of(cmd.asset_id) == _domain_of(creator)):
+            raise CommandError(2, 'No such permissions')
+        value = self._amount(state, cmd.asset_id, cmd.amount, 3)
+        state.balances[creator][cmd.asset_id] = self._add(
+            state.balance(creator, cmd.asset_id), value, 4, 'Summation overflow')
+
+    def _execute_subtract_asset_quantity(self, state, creator, cmd, genesis):
+        if not genesis and not state.has_permission(creator, 'can_subtract_asset_qty') \
+                and not (state.has_permission(creator, 'can_subtract_domain_asset_qty')
+                         and _domain_of(cmd.asset_id) == _domain_of(creator)):
+            raise CommandError(2, 'No such permissions')
+        value = self._amount(state, cmd.asset_id, cmd.amount, 3)
+        balance = state.balance(creator, cmd.asset_id)
+        if balance < value:
+            raise CommandError(4, 'Not enough balance')
+        state.balances[creator][cmd.asset_id] = balance - value
+
+    def _execute_add_peer(self, state, creator, cmd, genesis):
+        self._require(state, creator, genesis, 'can_add_peer')
+        if cmd.peer.peer_key in state.peers:
+            raise CommandError(1, 'Peer already exists')
+        peer = primitive_pb2.Peer()
+        peer.CopyFrom(cmd.peer)
+        state.peers[peer.peer_key] = peer
+
+    def _execute_remove_peer(self, state, creator, cmd, genesis):
+        self._require(state, creator, genesis, 'can_remove_peer')
+        if cmd.public_key not in state.peers:
+            raise CommandError(3, 'No such peer')
+        if len(state.peers) == 1:
+            raise CommandError(4, 'Cannot remove the last peer')
+        del state.peers[cmd.public_key]
+
+    def _execute_add_signatory(self, state, creator, cmd, genesis):
+        if not genesis and not (creator == cmd.account_id and state.has_permission(creator, 'can_add_signatory')) \
+                and not state.has_grant(cmd.account_id, creator, 'can_add_my_signatory') \
+                and not state.has_permission(creator, 'root'):
+            raise CommandError(2, 'No such permissions')
+        account = state.accounts.get(cmd.account_id)
+        if account is None:
+            raise CommandError(3, 'No such account')
+        if cmd.public_key.lower() in account.signatories:
+            raise CommandError(4, 'Signatory already exists')
+        account.signatories.append(cmd.public_key.lower())
+
+    def _execute_remove_signatory(self, state, creator, cmd, genesis):
+        if not genesis and not (creator == cmd.account_id and state.has_permission(creator, 'can_remove_signatory')) \
+                and not state.has_grant(cmd.account_id, creator, 'can_remove_my_signatory') \
+                and not state.has_permission(creator, 'root'):
+            raise CommandError(2, 'No such permissions')
+        account = state.accounts.get(cmd.account_id)
+        if account is None:
+            raise CommandError(3, 'No such account')
+        if cmd.public_key.lower() not in account.signatories:
+            raise CommandError(4, 'No such signatory')
+        if len(account.signatories) - 1 < account.quorum:
+            raise CommandError(5, 'Signatories count would be less than quorum')
+        account.signatories.remove(cmd.public_key.lower())
+
+    def _execute_append_role(self, state, creator, cmd, genesis):
+        self._require(state, creator, genesis, 'can_append_role')
+        if cmd.account_id not in state.accounts:
+            raise CommandError(3, 'No such account')
+        if cmd.role_name not in state.roles:
+            raise CommandError(4, 'No such role')
+        if not genesis and not state.has_permission(creator, 'root') \
+                and not state.roles[cmd.role_name] <= state.permissions(creator):
+            raise CommandError(2, 'No such permissions')
+        account = state.accounts[cmd.account_id]
+        if cmd.role_name not in account.roles:
+            account.roles.append(cmd.role_name)
+
+    def _execute_detach_role(self, state, creator, cmd, genesis):
+        self._require(state, creator, genesis, 'can_detach_role')
+        account = state.accounts.get(cmd.account_id)
+        if account is None:
+            raise CommandError(3, 'No such account')
+        if cmd.role_name not in state.roles:
+            raise CommandError(5, 'No such role')
+        if cmd.role_name not in account.roles:
+            raise CommandError(4, 'Account does not have the role')
+        account.roles.remove(cmd.role_name)
+
+    def _execute_create_account(self, state, creator, cmd, genesis):
+        self._require(state, creator, genesis, 'can_create_account')
+        if cmd.domain_id not in state.domains:
+            raise CommandError(3, 'No such domain')
+        account_id = '{}@{}'.format(cmd.account_name, cmd.domain_id)
+        if account_id in state.accounts:
+            raise CommandError(4, 'Account already exists')
+        state.accounts[account_id] = MockAccount(account_id, cmd.public_key, [state.domains[cmd.domain_id]])
+
+    def _execute_create_asset(self, state, creator, cmd, genesis):
+        self._require(state, creator, genesis, 'can_create_asset')
+        if cmd.domain_id not in state.domains:
+            raise CommandError(3, 'No such domain')
+        asset_id = '{}#{}'.format(cmd.asset_name, cmd.domain_id)
+        if asset_id in state.assets:
+            raise CommandError(4, 'Asset already exists')
+        state.assets[asset_id] = cmd.precision
+
+    def _execute_create_domain(self, state, creator, cmd, genesis):
+        self._require(state, creator, genesis, 'can_create_domain')
+        if cmd.domain_id in state.domains:
+            raise CommandError(3, 'Domain already exists')
+        if cmd.default_role not in state.roles:
+            raise CommandError(4, 'No default role found')
+        state.domains[cmd.domain_id] = cmd.default_role
+
+    def _execute_create_role(self, state, creator, cmd, genesis):
+        self._require(state, creator, genesis, 'can_create_role')
+        permissions = set(cmd.permissions)
+        if not genesis and not state.has_permission(creator, 'root') \
+                and not permissions <= state.permissions(creator):
+            raise CommandError(2, 'No such permissions')
+        if cmd.role_name in state.roles:
+            raise CommandError(3, 'Role already exists')
+        state.roles[cmd.role_name] = permissions
+
+    def _execute_grant_permission(self, state, creator, cmd, genesis):
+        permission_name = primitive_pb2.GrantablePermission.Name(cmd.permission)
+        self._require(state, creator, genesis, 'can_grant_' + permission_name)
+        if cmd.account_id not in state.accounts:
+            raise CommandError(3, 'No such account')
+        state.grants.add((creator, cmd.account_id, cmd.permission))
+
+    def _execute_revoke_permission(self, state, creator, cmd, genesis):
+        if cmd.account_id not in state.accounts:
+            raise CommandError(3, 'No such account')
+        grant = (creator, cmd.account_id, cmd.permission)
+        if grant not in state.grants:
+            raise CommandError(2, 'No such permissions')
+        state.grants.remove(grant)
+
+    def _check_detail_writer(self, state, creator, account_id, genesis):
+        if not genesis and creator != account_id and not state.has_permission(creator, 'can_set_detail') \
+                and not state.has_grant(account_id, creator, 'can_set_my_account_detail'):
+            raise CommandError(2, 'No such permissions')
+        account = state.accounts.get(account_id)
+        if account is None:
+            raise CommandError(3, 'No such account')
+        return account
+
+    def _execute_set_account_detail(self, state, creator, cmd, genesis):
+        account = self._check_detail_writer(state, creator, cmd.account_id, genesis)
+        account.details.setdefault(creator, collections.OrderedDict())[cmd.key] = cmd.value
+
+    def _execute_compare_and_set_account_detail(self, state, creator, cmd, genesis):
+        account = self._check_detail_writer(state, creator, cmd.account_id, genesis)
+        current = account.details.get(creator, {}).get(cmd.key)
+        if cmd.HasField('old_value'):
+            if current != cmd.old_value and not (current is None and not cmd.check_empty):
+                raise CommandError(4, 'Old value mismatch')
+        elif current is not None:
+            raise CommandError(4, 'Old value mismatch')
+        account.details.setdefault(creator, collections.OrderedDict())[cmd.key] = cmd.value
+
+    def _execute_set_account_quorum(self, state, creator, cmd, genesis):
+        if not genesis and not (creator == cmd.account_id and state.has_permission(creator, 'can_set_quorum')) \
+                and not state.has_grant(cmd.account_id, creator, 'can_set_my_quorum') \
+                and not state.has_permission(creator, 'root'):
+            raise CommandError(2, 'No such permissions')
+        account = state.accounts.get(cmd.account_id)
+        if account is None:
+            raise CommandError(3, 'No such account')
+        if not 1 <= cmd.quorum <= len(account.signatories):
+            raise CommandError(5, 'New quorum is incorrect')
+        account.quorum = cmd.quorum
+
+    def _execute_transfer_asset(self, state, creator, cmd, genesis):
+        if not genesis and not (creator == cmd.src_account_id and state.has_permission(creator, 'can_transfer')) \
+                and not state.has_grant(cmd.src_account_id, creator, 'can_transfer_my_assets') \
+                and not state.has_permission(creator, 'root'):
+            raise CommandError(2, 'No such permissions')
+        if cmd.src_account_id not in state.accounts:
+            raise CommandError(3, 'No such source account')
+        if cmd.dest_account_id not in state.accounts:
+            raise CommandError(4, 'No such destination account')
+        if not genesis and not state.has_permission(cmd.dest_account_id, 'can_receive'):
+            raise CommandError(2, 'No such permissions')
+        value = self._amount(state, cmd.asset_id, cmd.amount, 5)
+        source_balance = state.balance(cmd.src_account_id, cmd.asset_id)
+        if source_balance < value:
+            raise CommandError(6, 'Not enough balance')
+        destination_balance = self._add(
+            state.balance(cmd.dest_account_id, cmd.asset_id), value, 7, 'Too much asset to transfer')
+        state.balances[cmd.src_account_id][cmd.asset_id] = source_balance - value
+        state.balances[cmd.dest_account_id][cmd.asset_id] = destination_balance
+
+    def _execute_set_setting_value(self, state, creator, cmd, genesis):
+        if not genesis:
+            raise CommandError(2, 'Settings can be changed only in genesis block')
+        state.settings[cmd.key] = cmd.value
+
+    def _execute_call_engine(self, state, creator, cmd, genesis):
+        raise CommandError(1, 'Engine is not available')
+
+    # *** QueryService_v1 *** #
+
+    def Healthcheck(self, request, context):
+        with self._lock:
+            return qry_responses_pb2.HealthcheckData(
+                memory_consumption=0, is_healthy=True, is_syncing=False,
+                last_block_height=len(self.blocks), last_block_reject=0)
+
+    def Find(self, request, context):
+        response = qry_responses_pb2.QueryResponse()
+        response.query_hash = _hex_hash(request)
+        with self._lock:
+            self._expire_pending()
+            try:
+                creator = self._check_query_signature(request, request.payload.meta)
+                field_name = request.payload.WhichOneof('query')
+                if field_name is None:
+                    raise QueryFailure(qry_responses_pb2.ErrorResponse.NOT_SUPPORTED, 'Empty query')
+                getattr(self, '_query_' + field_name)(creator, getattr(request.payload, field_name), response)
+            except QueryFailure as failure:
+                response.ClearField('error_response')
+                response.error_response.reason = failure.reason
+                response.error_response.message = failure.message
+                response.error_response.error_code = failure.error_code
+        return response
+
+    def FetchCommits(self, request, context):
+        with self._lock:
+            try:
+                creator = self._check_query_signature(request, request.meta)
+                self._check_query_permission(creator, None, 'can_get_blocks')
+            except QueryFailure as failure:
+                yield qry_responses_pb2.BlockQueryResponse(
+                    block_error_response=qry_responses_pb2.BlockErrorResponse(message=failure.message))
+                return
+            sent = len(self.blocks)
+        while context.is_active():
+            with self._condition:
+                if len(self.blocks) <= sent:
+                    self._condition.wait(0.1)
+                new_blocks = self.blocks[sent:]
+            for block in new_blocks:
+                response = qry_responses_pb2.BlockQueryResponse()
+                response.block_response.block.CopyFrom(block)
+                yield response
+            sent += len(new_blocks)
+
+    def _check_query_signature(self, query, meta):
+        creator = meta.creator_account_id
+        if not query.HasField('signature') or not IrohaCrypto.is_any_signature_valid(query, query.signature):
+            raise QueryFailure(qry_responses_pb2.ErrorResponse.STATELESS_INVALID, 'Bad query signature')
+        account = self.state.accounts.get(creator)
+        if account is None or query.signature.public_key.lower() not in account.signatories:
+            raise QueryFailure(qry_responses_pb2.ErrorResponse.STATEFUL_INVALID,
+                               'Query signatory is not a signatory of the creator', 3)
+        return creator
+
+    def _check_query_permission(self, creator, account_id, all_permission, domain_permission=None,
+                                my_permission=None):
+        state = self.state
+        if state.has_permission(creator, all_permission):
+            return
+        if domain_permission and account_id is not None and _domain_of(account_id) == _domain_of(creator) \
+                and state.has_permission(creator, domain_permission):
+            return
+        if my_permission and account_id == creator and state.has_permission(creator, my_permission):
+            return
+        raise QueryFailure(qry_responses_pb2.ErrorResponse.STATEFUL_INVALID, 'No such permissions', 2)
+
+    def _existing_account(self, account_id, reason=qry_responses_pb2.ErrorResponse.NO_ACCOUNT):
+        account = self.state.accounts.get(account_id)
+        if account is None:
+            raise QueryFailure(reason, 'No account {}'.format(account_id))
+        return account
+
+    def _query_get_account(self, creator, query, response):
+        self._check_query_permission(creator, query.account_id, 'can_get_all_accounts',
+                                     'can_get_domain_accounts', 'can_get_my_account')
+        account = self._existing_account(query.account_id)
+        result = response.account_response
+        result.account.account_id = account.account_id
+        result.account.domain_id = account.domain_id
+        result.account.quorum = account.quorum
+        result.account.json_data = account.json_data()
+        result.account_roles.extend(account.roles)
+
+    def _query_get_signatories(self, creator, query, response):
+        self._check_query_permission(creator, query.account_id, 'can_get_all_signatories',
+                                     'can_get_domain_signatories', 'can_get_my_signatories')
+        account = self._existing_account(query.account_id, qry_responses_pb2.ErrorResponse.NO_SIGNATORIES)
+        response.signatories_response.keys.extend(account.signatories)
+
+    def _query_get_account_transactions(self, creator, query, response):
+        self._check_query_permission(creator, query.account_id, 'can_get_all_acc_txs',
+                                     'can_get_domain_acc_txs', 'can_get_my_acc_txs')
+        self._existing_account(query.account_id)
+        self._transactions_page(
+            query.pagination_meta, response,
+            lambda tx: tx.payload.reduced_payload.creator_account_id == query.account_id)
+
+    def _query_get_account_asset_transactions(self, creator, query, response):
+        self._check_query_permission(creator, query.account_id, 'can_get_all_acc_ast_txs',
+                                     'can_get_domain_acc_ast_txs', 'can_get_my_acc_ast_txs')
+        self._existing_account(query.account_id)
+        if query.asset_id not in self.state.assets:
+            raise QueryFailure(qry_responses_pb2.ErrorResponse.NO_ASSET, 'No asset {}'.format(query.asset_id))
+
+        def involves(tx):
+            creator_id = tx.payload.reduced_payload.creator_account_id
+            for command in tx.payload.reduced_payload.commands:
+                field_name = command.WhichOneof('command')
+                internal_command = getattr(command, field_name)
+                if field_name in ('add_asset_quantity', 'subtract_asset_quantity') \
+                        and creator_id == query.account_id and internal_command.asset_id == query.asset_id:
+                    return True
+                if field_name == 'transfer_asset' and internal_command.asset_id == query.asset_id \
+                        and query.account_id in (internal_command.src_account_id, internal_command.dest_account_id):
+                    return True
+            return False
+
+        self._transactions_page(query.pagination_meta, response, involves)
+
+    def _transactions_page(self, pagination_meta, response, predicate):
+        if pagination_meta.page_size == 0:
+            raise QueryFailure(qry_responses_pb2.ErrorResponse.STATELESS_INVALID, 'Page size has to be positive')
+        entries = []
+        for height, position, tx_hash, tx in self._committed:
+            if not predicate(tx):
+                continue
+            created_time = tx.payload.reduced_payload.created_time
+            if pagination_meta.HasField('first_tx_time') \
+                    and created_time < pagination_meta.first_tx_time.ToMilliseconds():
+                continue
+            if pagination_meta.HasField('last_tx_time') \
+                    and created_time > pagination_meta.last_tx_time.ToMilliseconds():
+                continue
+            if pagination_meta.HasField('first_tx_height') and height < pagination_meta.first_tx_height:
+                continue
+            if pagination_meta.HasField('last_tx_height') and height > pagination_meta.last_tx_height:
+                continue
+            entries.append((height, position, tx_hash, tx))
+        for field_ordering in reversed(pagination_meta.ordering.sequence):
+            if field_ordering.field == queries_pb2.kCreatedTime:
+                key = lambda entry: entry[3].payload.reduced_payload.created_time
+            else:
+                key = lambda entry: (entry[0], entry[1])
+            entries.sort(key=key, reverse=field_ordering.direction == queries_pb2.kDescending)
+        start = 0
+        if pagination_meta.HasField('first_tx_hash'):
+            hashes = [entry[2] for entry in entries]
+            if pagination_meta.first_tx_hash not in hashes:
+                raise QueryFailure(qry_responses_pb2.ErrorResponse.STATEFUL_INVALID, 'Invalid pagination hash', 4)
+            start = hashes.index(pagination_meta.first_tx_hash)
+        page = entries[start:start + pagination_meta.page_size]
+        result = response.transactions_page_response
+        result.transactions.extend(entry[3] for entry in page)
+        result.all_transactions_size = len(entries)
+        if start + pagination_meta.page_size < len(entries):
+            result.next_tx_hash = entries[start + pagination_meta.page_size][2]
+
+    def _query_get_transactions(self, creator, query, response):
+        by_hash = {tx_hash: tx for _, _, tx_hash, tx in self._committed}
+        result = response.transactions_response
+        for tx_hash in query.tx_hashes:
+            tx = by_hash.get(tx_hash.lower())
+            if tx is None:
+                raise QueryFailure(qry_responses_pb2.ErrorResponse.STATEFUL_INVALID,
+                                   'No transaction {}'.format(tx_hash), 4)
+            if not self.state.has_permission(creator, 'can_get_all_txs') \
+                    and not (tx.payload.reduced_payload.creator_account_id == creator
+                             and self.state.has_permission(creator, 'can_get_my_txs')):
+                raise QueryFailure(qry_responses_pb2.ErrorResponse.STATEFUL_INVALID, 'No such permissions', 2)
+            result.transactions.add().CopyFrom(tx)
+        if not query.tx_hashes:
+            result.SetInParent()
+
+    def _query_get_account_assets(self, creator, query, response):
+        self._check_query_permission(creator, query.account_id, 'can_get_all_acc_ast',
+                                     'can_get_domain_acc_ast', 'can_get_my_acc_ast')
+        self._existing_account(query.account_id)
+        balances = sorted(self.state.balances[query.account_id].items())
+        start = 0
+        page_size = len(balances)
+        if query.HasField('pagination_meta'):
+            page_size = query.pagination_meta.page_size
+            if page_size == 0:
+                raise QueryFailure(qry_responses_pb2.ErrorResponse.STATELESS_INVALID, 'Page size has to be positive')
+            if query.pagination_meta.HasField('first_asset_id'):
+                asset_ids = [asset_id for asset_id, _ in balances]
+                if query.pagination_meta.first_asset_id not in asset_ids:
+                    raise QueryFailure(qry_responses_pb2.ErrorResponse.STATEFUL_INVALID, 'Invalid pagination asset', 4)
+                start = asset_ids.index(query.pagination_meta.first_asset_id)
+        result = response.account_assets_response
+        result.SetInParent()
+        for asset_id, balance in balances[start:start + page_size]:
+            account_asset = result.account_assets.add()
+            account_asset.asset_id = asset_id
+            account_asset.account_id = query.account_id
+            account_asset.balance = str(balance)
+        result.total_number = len(balances)
+        if start + page_size < len(balances):
+            result.next_asset_id = balances[start + page_size][0]
+
+    def _query_get_account_detail(self, creator, query, response):
+        account_id = query.account_id if query.HasField('account_id') else creator
+        self._check_query_permission(creator, account_id, 'can_get_all_acc_detail',
+                                     'can_get_domain_acc_detail', 'can_get_my_acc_detail')
+        account = self._existing_account(account_id)
+        records = [(writer, key, value)
+                   for writer, details in account.details.items()
+                   for key, value in details.items()
+                   if (not query.HasField('writer') or writer == query.writer)
+                   and (not query.HasField('key') or key == query.key)]
+        start = 0
+        page_size = len(records)
+        if query.HasField('pagination_meta'):
+            page_size = query.pagination_meta.page_size
+            if page_size == 0:
+                raise QueryFailure(qry_responses_pb2.ErrorResponse.STATELESS_INVALID, 'Page size has to be positive')
+            if query.pagination_meta.HasField('first_record_id'):
+                first = (query.pagination_meta.first_record_id.writer, query.pagination_meta.first_record_id.key)
+                record_ids = [(writer, key) for writer, key, _ in records]
+                if first not in record_ids:
+                    raise QueryFailure(qry_responses_pb2.ErrorResponse.STATEFUL_INVALID, 'Invalid pagination record', 4)
+                start = record_ids.index(first)
+        page = collections.OrderedDict()
+        for writer, key, value in records[start:start + page_size]:
+            page.setdefault(writer, collections.OrderedDict())[key] = value
+        result = response.account_detail_response
+        result.detail = json.dumps(page)
+        result.total_number = len(records)
+        if start + page_size < len(records):
+            writer, key, _ = records[start + page_size]
+            result.next_record_id.writer = writer
+            result.next_record_id.key = key
+
+    def _query_get_roles(self, creator, query, response):
+        self._check_query_permission(creator, None, 'can_get_roles')
+        if not self.state.roles:
+            raise QueryFailure(qry_responses_pb2.ErrorResponse.NO_ROLES, 'No roles')
+        response.roles_response.roles.extend(self.state.roles.keys())
+
+    def _query_get_role_permissions(self, creator, query, response):
+        self._check_query_permission(creator, None, 'can_get_roles')
+        permissions = self.state.roles.get(query.role_id)
+        if permissions is None:
+            raise QueryFailure(qry_responses_pb2.ErrorResponse.NO_ROLES, 'No role {}'.format(query.role_id))
+        response.role_permissions_response.SetInParent()
+        response.role_permissions_response.permissions.extend(sorted(permissions))
+
+    def _query_get_asset_info(self, creator, query, response):
+        self._check_query_permission(creator, None, 'can_read_assets')
+        if query.asset_id not in self.state.assets:
+            raise QueryFailure(qry_responses_pb2.ErrorResponse.NO_ASSET, 'No asset {}'.format(query.asset_id))
+        asset = response.asset_response.asset
+        asset.asset_id = query.asset_id
+        asset.domain_id = _domain_of(query.asset_id)
+        asset.precision = self.state.assets[query.asset_id]
+
+    def _query_get_pending_transactions(self, creator, query, response):
+        pending = [(tx_hash, tx) for tx_hash, (tx, _) in self._pending.items()
+                   if tx.payload.reduced_payload.creator_account_id == creator]
+        result = response.pending_transactions_page_response
+        result.SetInParent()
+        result.all_transactions_size = len(pending)
+        start = 0
+        page_size = len(pending)
+        if query.HasField('pagination_meta'):
+            page_size = query.pagination_meta.page_size
+            if page_size == 0:
+                raise QueryFailure(qry_responses_pb2.ErrorResponse.STATELESS_INVALID, 'Page size has to be positive')
+            if query.pagination_meta.HasField('first_tx_hash'):
+                hashes = [tx_hash for tx_hash, _ in pending]
+                if query.pagination_meta.first_tx_hash not in hashes:
+                    raise QueryFailure(qry_responses_pb2.ErrorResponse.STATEFUL_INVALID, 'Invalid pagination hash', 4)
+                start = hashes.index(query.pagination_meta.first_tx_hash)
+        end = start + page_size
+        result.transactions.extend(tx for _, tx in pending[start:end])
+        if end < len(pending):
+            next_hash, next_tx = pending[end]
+            batch_size = len(next_tx.payload.batch.reduced_hashes) if next_tx.payload.HasField('batch') else 1
+            result.next_batch_info.first_tx_hash = next_hash
+            result.next_batch_info.batch_size = batch_size
+
+    def _query_get_block(self, creator, query, response):
+        self._check_query_permission(creator, None, 'can_get_blocks')
+        if not 1 <= query.height <= len(self.blocks):
+            raise QueryFailure(qry_responses_pb2.ErrorResponse.STATEFUL_INVALID,
+                               'Invalid height {}'.format(query.height), 3)
+        response.block_response.block.CopyFrom(self.blocks[query.height - 1])
+
+    def _query_get_peers(self, creator, query, response):
+        self._check_query_permission(creator, None, 'can_get_peers')
+        response.peers_response.SetInParent()
+        response.peers_response.peers.extend(self.state.peers.values())
+
+    def _query_get_engine_receipts(self, creator, query, response):
+        self._check_query_permission(creator, None, 'can_get_all_engine_receipts',
+                                     my_permission='can_get_my_engine_receipts')
+        response.engine_receipts_response.SetInParent()
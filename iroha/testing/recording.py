@@ -0,0 +1,308 @@
+#!/usr/bin/env python3
+#
+# Copyright Soramitsu Co., Ltd. All Rights Reserved.
+# SPDX-License-Identifier: Apache-2.0
+#
+
+import base64
+import json
+import threading
+
+import grpc
+from google.protobuf import empty_pb2
+from google.protobuf import text_format
+
+from .. import endpoint_pb2
+from .. import qry_responses_pb2
+from .. import queries_pb2
+from .. import transaction_pb2
+from ..iroha import IrohaGrpc
+from .faults import InjectedRpcError
+
+FORMAT_NAME = 'iroha-python-recording'
+FORMAT_VERSION = 1
+
+# request and response message types of each recorded method
+METHOD_TYPES = {
+    'Torii': (transaction_pb2.Transaction, empty_pb2.Empty),
+    'ListTorii': (endpoint_pb2.TxList, empty_pb2.Empty),
+    'Status': (endpoint_pb2.TxStatusRequest, endpoint_pb2.ToriiResponse),
+    'StatusStream': (endpoint_pb2.TxStatusRequest, endpoint_pb2.ToriiResponse),
+    'Find': (queries_pb2.Query, qry_responses_pb2.QueryResponse),
+    'FetchCommits': (queries_pb2.BlocksQuery, qry_responses_pb2.BlockQueryResponse),
+    'Healthcheck': (empty_pb2.Empty, qry_responses_pb2.HealthcheckData),
+}
+STREAM_METHODS = ('StatusStream', 'FetchCommits')
+COMMAND_METHODS = ('Torii', 'ListTorii', 'Status', 'StatusStream')
+QUERY_METHODS = ('Find', 'FetchCommits', 'Healthcheck')
+
+
+class ReplayMismatchError(AssertionError):
+    """
+    Call made during replay differs from the recorded one
+    """
+
+
+def _encode(message):
+    return base64.b64encode(message.SerializeToString()).decode('ascii')
+
+
+def _decode(message_type, data):
+    message = message_type()
+    message.ParseFromString(base64.b64decode(data))
+    return message
+
+
+def _normalize_transaction(transaction):
+    transaction.payload.reduced_payload.created_time = 0
+    if transaction.payload.HasField('batch'):
+        transaction.payload.batch.ClearField('reduced_hashes')
+    transaction.ClearField('signatures')
+
+
+def normalize_request(method, request):
+    """
+    Drop the fields which differ between runs of the same scenario:
+    creation times, query counters, signatures and hashes derived from them
+    :param method: name of RPC method
+    :param request: protobuf request of the method
+    :return: normalized copy of the request
+    """
+    normalized = type(request)()
+    normalized.CopyFrom(request)
+    if method == 'Torii':
+        _normalize_transaction(normalized)
+    elif method == 'ListTorii':
+        for transaction in normalized.transactions:
+            _normalize_transaction(transaction)
+    elif method in ('Status', 'StatusStream'):
+        normalized.ClearField('tx_hash')
+    elif method == 'Find':
+        normalized.payload.meta.created_time = 0
+        normalized.payload.meta.query_counter = 0
+        normalized.ClearField('signature')
+    elif method == 'FetchCommits':
+        normalized.meta.created_time = 0
+        normalized.meta.query_counter = 0
+        normalized.ClearField('signature')
+    return normalized
+
+
+class Interaction(object):
+    """
+    Single recorded call
+    """
+
+    def __init__(self, method, request, responses=None, error=None):
+        """
+        :param method: name of RPC method
+        :param request: protobuf request
+        :param responses: list of protobuf responses, a single one for unary methods
+        :param error: optional tuple of grpc.StatusCode name and details the call failed with
+        """
+        self.method = method
+        self.request = request
+        self.responses = list(responses) if responses else []
+        self.error = error
+
+    def to_dict(self):
+        entry = {
+            'method': self.method,
+            'request': _encode(self.request),
+            'responses': [_encode(response) for response in self.responses],
+        }
+        if self.error is not None:
+            entry['error'] = {'code': self.error[0], 'details': self.error[1]}
+        return entry
+
+    @classmethod
+    def from_dict(cls, entry):
+        method = entry['method']
+        if method not in METHOD_TYPES:
+            raise ValueError('Unknown RPC method {} in the recording'.format(method))
+        request_type, response_type = METHOD_TYPES[method]
+        error = entry.get('error')
+        return cls(method, _decode(request_type, entry['request']),
+                   [_decode(response_type, data) for data in entry['responses']],
+                   (error['code'], error['details']) if error else None)
+
+    def raise_error(self):
+        if self.error is not None:
+            raise InjectedRpcError(grpc.StatusCode[self.error[0]], self.error[1])
+
+
+def save_interactions(path, interactions):
+    """
+    Store recorded calls in a file
+    :param path: path of the file
+    :param interactions: list of Interaction
+    """
+    with open(path, 'w') as recording_file:
+        json.dump({
+            'format': FORMAT_NAME,
+            'version': FORMAT_VERSION,
+            'interactions': [interaction.to_dict() for interaction in interactions],
+        }, recording_file, indent=2)
+
+
+def load_interactions(path):
+    """
+    Read recorded calls from a file
+    :param path: path of the file
+    :return: list of Interaction
+    :raise: ValueError if the file is not a recording or its version is not supported
+    """
+    with open(path, 'r') as recording_file:
+        data = json.load(recording_file)
+    if data.get('format') != FORMAT_NAME:
+        raise ValueError('{} is not a recording of Iroha calls'.format(path))
+    if data.get('version') != FORMAT_VERSION:
+        raise ValueError('Recording version {} is not supported, version {} expected'.format(
+            data.get('version'), FORMAT_VERSION))
+    return [Interaction.from_dict(entry) for entry in data['interactions']]
+
+
+class _Stub(object):
+    """
+    Proxy of a generated gRPC stub routing each call through a handler
+    """
+
+    def __init__(self, methods, handler):
+        for method in methods:
+            setattr(self, method, self._wrap(method, handler))
+
+    @staticmethod
+    def _wrap(method, handler):
+        def call(request, timeout=None):
+            return handler(method, request, timeout)
+        return call
+
+
+class RecordingGrpc(IrohaGrpc):
+    """
+    IrohaGrpc transport storing all the calls made to the peer together with their results
+    """
+
+    def __init__(self, *args, **kwargs):
+        """
+        Accepts the same arguments as IrohaGrpc
+        """
+        super().__init__(*args, **kwargs)
+        self._lock = threading.Lock()
+        self.interactions = []
+        self._stubs = {
+            method: self._command_service_stub if method in COMMAND_METHODS else self._query_service_stub
+            for method in METHOD_TYPES
+        }
+        self._command_service_stub = _Stub(COMMAND_METHODS, self._record)
+        self._query_service_stub = _Stub(QUERY_METHODS, self._record)
+
+    def save(self, path):
+        """
+        Store the recorded calls in a file
+        :param path: path of the file
+        """
+        with self._lock:
+            interactions = list(self.interactions)
+        save_interactions(path, interactions)
+
+    def _record(self, method, request, timeout):
+        request_copy = type(request)()
+        request_copy.CopyFrom(request)
+        interaction = Interaction(method, request_copy)
+        with self._lock:
+            self.interactions.append(interaction)
+        call = getattr(self._stubs[method], method)
+        if method in STREAM_METHODS:
+            return self._record_stream(interaction, call(request, timeout=timeout))
+        try:
+            response = call(request, timeout=timeout)
+        except grpc.RpcError as rpc_error:
+            interaction.error = (rpc_error.code().name, rpc_error.details())
+            raise
+        interaction.responses.append(response)
+        return response
+
+    @staticmethod
+    def _record_stream(interaction, responses):
+        try:
+            for response in responses:
+                interaction.responses.append(response)
+                yield response
+        except grpc.RpcError as rpc_error:
+            interaction.error = (rpc_error.code().name, rpc_error.details())
+            raise
+
+
+class ReplayGrpc(IrohaGrpc):
+    """
+    IrohaGrpc transport answering calls with recorded results without contacting any peer.
+    Calls have to be made in the recorded order, any difference raises ReplayMismatchError.
+    """
+
+    def __init__(self, path_or_interactions, strict=True, timeout=None):
+        """
+        :param path_or_interactions: path of a recording file or list of Interaction
+        :param strict: compare requests byte by byte, otherwise creation times, query counters,
+        signatures and transaction hashes are ignored, see normalize_request
+        :param timeout: timeout for network I/O operations in seconds, not used by replay itself
+        """
+        super().__init__(timeout=timeout)
+        if isinstance(path_or_interactions, str):
+            path_or_interactions = load_interactions(path_or_interactions)
+        self._interactions = list(path_or_interactions)
+        self._position = 0
+        self._strict = strict
+        self._lock = threading.Lock()
+        self._command_service_stub = _Stub(COMMAND_METHODS, self._replay)
+        self._query_service_stub = _Stub(QUERY_METHODS, self._replay)
+
+    @property
+    def remaining(self):
+        """Number of recorded calls not replayed yet"""
+        with self._lock:
+            return len(self._interactions) - self._position
+
+    def assert_exhausted(self):
+        """
+        :raise: ReplayMismatchError if some of the recorded calls have not been made
+        """
+        with self._lock:
+            if self._position < len(self._interactions):
+                pending = [interaction.method for interaction in self._interactions[self._position:]]
+                raise ReplayMismatchError('{} recorded calls have not been replayed: {}'.format(
+                    len(pending), ', '.join(pending)))
+
+    def _replay(self, method, request, timeout):
+        interaction = self._match(method, request)
+        if method in STREAM_METHODS:
+            return self._replay_stream(interaction)
+        interaction.raise_error()
+        if not interaction.responses:
+            raise ReplayMismatchError('Recorded {} call has no response'.format(method))
+        return interaction.responses[0]
+
+    @staticmethod
+    def _replay_stream(interaction):
+        yield from interaction.responses
+        interaction.raise_error()
+
+    def _match(self, method, request):
+        with self._lock:
+            index = self._position
+            if index >= len(self._interactions):
+                raise ReplayMismatchError('Unexpected {} call #{}, the recording has only {} calls'.format(
+                    method, index + 1, len(self._interactions)))
+            interaction = self._interactions[index]
+            if interaction.method != method:
+                raise ReplayMismatchError('Call #{} is {} while {} was recorded'.format(
+                    index + 1, method, interaction.method))
+            expected, actual = interaction.request, request
+            if not self._strict:
+                expected, actual = normalize_request(method, expected), normalize_request(method, actual)
+            if expected.SerializeToString(deterministic=True) != actual.SerializeToString(deterministic=True):
+                raise ReplayMismatchError('Call #{} {} has a different request.\nRecorded:\n{}\nActual:\n{}'.format(
+                    index + 1, method, text_format.MessageToString(expected),
+                    text_format.MessageToString(actual)))
+            self._position += 1
+            return interaction
@@ -0,0 +1,229 @@
+#!/usr/bin/env python3
+#
+# Copyright Soramitsu Co., Ltd. All Rights Reserved.
+# SPDX-License-Identifier: Apache-2.0
+#
+
+"""
+Generator of docker compose directories with a multi-peer Iroha network.
+Keys are derived from a seed, so the same arguments always produce the same files.
+"""
+
+import argparse
+import collections
+import hashlib
+import os
+import stat
+import sys
+
+from . import protojson
+from .config import IrohaConfig
+from .genesis import example_genesis
+from .iroha import IrohaCrypto
+
+TORII_PORT = 50051
+INTERNAL_PORT = 10001
+POSTGRES_USER = 'postgres'
+POSTGRES_PASSWORD = 'mysecretpassword'
+
+ENTRYPOINT = '''#!/usr/bin/env bash
+
+echo key=$KEY
+if [ -n "$IROHA_POSTGRES_HOST" ]; then
+  PG_PORT=${IROHA_POSTGRES_PORT:-5432}
+  /wait-for-it.sh -h $IROHA_POSTGRES_HOST -p $PG_PORT -t 30 -- true
+fi
+irohad --genesis_block genesis.block --config config.docker --keypair_name $KEY --overwrite-ledger
+'''
+
+
+def derive_keypair(seed, name):
+    """
+    Derive a keypair deterministically, the keys are for test networks only
+    :param seed: seed of the network
+    :param name: name of the key owner, e.g. node0 or admin@test
+    :return: tuple of hex private and public keys
+    """
+    private_key = hashlib.sha3_256('{}/{}'.format(seed, name).encode('utf-8')).hexdigest()
+    return private_key, IrohaCrypto.derive_public_key(private_key).decode('utf-8')
+
+
+class TestnetPeer(object):
+    """
+    Peer of a generated network
+    """
+
+    def __init__(self, index, seed, torii_host_port):
+        """
+        :param index: number of the peer starting from zero
+        :param seed: seed of the network the keys are derived from
+        :param torii_host_port: port Torii of the peer is published on the host
+        """
+        self.index = index
+        self.name = 'iroha{}'.format(index)
+        self.key_name = 'node{}'.format(index)
+        self.private_key, self.public_key = derive_keypair(seed, self.key_name)
+        self.address = '{}:{}'.format(self.name, INTERNAL_PORT)
+        self.postgres_host = 'postgres{}'.format(index)
+        self.torii_host_port = torii_host_port
+
+    def config(self, mst_enable=True):
+        """
+        :param mst_enable: whether multi-signature transactions are enabled
+        :return: IrohaConfig of the peer written to config.docker
+        """
+        return IrohaConfig.from_dict(collections.OrderedDict([
+            ('block_store_path', '/tmp/block_store/'),
+            ('torii_port', TORII_PORT),
+            ('internal_port', INTERNAL_PORT),
+            ('pg_opt', 'host={} port=5432 user={} password={}'.format(
+                self.postgres_host, POSTGRES_USER, POSTGRES_PASSWORD)),
+            ('max_proposal_size', 10),
+            ('proposal_delay', 5000),
+            ('vote_delay', 5000),
+            ('load_delay', 5000),
+            ('mst_enable', mst_enable),
+        ]))
+
+    def __repr__(self):
+        return 'TestnetPeer(name={!r}, address={!r}, torii_host_port={})'.format(
+            self.name, self.address, self.torii_host_port)
+
+
+class Testnet(object):
+    """
+    Files of an N-peer network: per-peer directories with config.docker, keys,
+    the shared genesis.block and entrypoint.sh, admin keys and docker-compose.yaml
+    """
+
+    def __init__(self, peers=4, seed='iroha-testnet', torii_host_port=TORII_PORT,
+                 iroha_image='hyperledger/iroha:latest', postgres_image='postgres:9.5', mst_enable=True):
+        """
+        :param peers: number of peers
+        :param seed: seed the keys of peers and accounts are derived from
+        :param torii_host_port: host port of Torii of the first peer, the following peers get the next ports
+        :param iroha_image: docker image of Iroha
+        :param postgres_image: docker image of PostgreSQL
+        :param mst_enable: whether multi-signature transactions are enabled
+        """
+        assert peers >= 1, 'At least one peer is required'
+        self.seed = seed
+        self.peers = [TestnetPeer(index, seed, torii_host_port + index) for index in range(peers)]
+        self.admin_private_key, self.admin_public_key = derive_keypair(seed, 'admin@test')
+        self.test_private_key, self.test_public_key = derive_keypair(seed, 'test@test')
+        self.iroha_image = iroha_image
+        self.postgres_image = postgres_image
+        self.mst_enable = mst_enable
+
+    def genesis(self):
+        """
+        :return: GenesisBuilder with every peer and the example roles, domain and accounts
+        """
+        return example_genesis([(peer.address, peer.public_key) for peer in self.peers],
+                               self.admin_public_key, self.test_public_key)
+
+    def compose(self):
+        """
+        :return: text of docker-compose.yaml
+        """
+        lines = ["version: '3.5'", '', 'networks:', '  iroha:', '', 'services:']
+        for peer in self.peers:
+            lines += [
+                '  {}:'.format(peer.name),
+                '    image: {}'.format(self.iroha_image),
+                '    container_name: {}'.format(peer.name),
+                '    depends_on:',
+                '      - {}'.format(peer.postgres_host),
+                '    restart: always',
+                '    tty: true',
+                '    environment:',
+                '      - KEY=keys/{}'.format(peer.key_name),
+                '      - IROHA_POSTGRES_HOST={}'.format(peer.postgres_host),
+                '      - IROHA_POSTGRES_PORT=5432',
+                '    entrypoint:',
+                '      - /opt/iroha_data/entrypoint.sh',
+                '    ports:',
+                "      - '{}:{}'".format(peer.torii_host_port, TORII_PORT),
+                '    networks:',
+                '      - iroha',
+                '    volumes:',
+                '      - ./{}:/opt/iroha_data'.format(peer.name),
+                '',
+                '  {}:'.format(peer.postgres_host),
+                '    image: {}'.format(self.postgres_image),
+                '    container_name: {}-{}'.format(peer.name, peer.postgres_host),
+                '    environment:',
+                '      - POSTGRES_USER={}'.format(POSTGRES_USER),
+                '      - POSTGRES_PASSWORD={}'.format(POSTGRES_PASSWORD),
+                '    networks:',
+                '      - iroha',
+                '    logging:',
+                '      driver: none',
+                '',
+            ]
+        return '\n'.join(lines)
+
+    def files(self):
+        """
+        :return: ordered dict of relative paths to file contents
+        """
+        genesis = protojson.to_json(self.genesis().build()) + '\n'
+        files = collections.OrderedDict()
+        files['docker-compose.yaml'] = self.compose()
+        files['keys/admin@test.priv'] = self.admin_private_key
+        files['keys/admin@test.pub'] = self.admin_public_key
+        files['keys/test@test.priv'] = self.test_private_key
+        files['keys/test@test.pub'] = self.test_public_key
+        for peer in self.peers:
+            files['{}/config.docker'.format(peer.name)] = peer.config(self.mst_enable).to_json() + '\n'
+            files['{}/genesis.block'.format(peer.name)] = genesis
+            files['{}/entrypoint.sh'.format(peer.name)] = ENTRYPOINT
+            files['{}/keys/{}.priv'.format(peer.name, peer.key_name)] = peer.private_key
+            files['{}/keys/{}.pub'.format(peer.name, peer.key_name)] = peer.public_key
+        return files
+
+    def write(self, directory):
+        """
+        Write the network files, existing files are overwritten
+        :param directory: target directory, created if missing
+        :return: list of written paths
+        """
+        written = []
+        for relative_path, content in self.files().items():
+            path = os.path.join(directory, *relative_path.split('/'))
+            os.makedirs(os.path.dirname(path), exist_ok=True)
+            with open(path, 'w') as network_file:
+                network_file.write(content)
+            if relative_path.endswith('entrypoint.sh'):
+                os.chmod(path, os.stat(path).st_mode | stat.S_IXUSR | stat.S_IXGRP | stat.S_IXOTH)
+            written.append(path)
+        return written
+
+
+def main(argv=None):
+    """
+    Command line entry point, see --help
+    :param argv: arguments without the program name, sys.argv by default
+    :return: exit code
+    """
+    parser = argparse.ArgumentParser(prog='python -m iroha.testnet', description=__doc__.strip())
+    parser.add_argument('directory', help='output directory')
+    parser.add_argument('--peers', type=int, default=4, help='number of peers')
+    parser.add_argument('--seed', default='iroha-testnet', help='seed the keys are derived from')
+    parser.add_argument('--torii-port', type=int, default=TORII_PORT, help='host port of Torii of the first peer')
+    parser.add_argument('--iroha-image', default='hyperledger/iroha:latest')
+    parser.add_argument('--postgres-image', default='postgres:9.5')
+    parser.add_argument('--no-mst', action='store_true', help='disable multi-signature transactions')
+    arguments = parser.parse_args(argv)
+    if arguments.peers < 1:
+        parser.error('at least one peer is required')
+    testnet = Testnet(arguments.peers, arguments.seed, arguments.torii_port,
+                      arguments.iroha_image, arguments.postgres_image, not arguments.no_mst)
+    testnet.write(arguments.directory)
+    for peer in testnet.peers:
+        print('{} torii 127.0.0.1:{} key {}'.format(peer.name, peer.torii_host_port, peer.public_key))
+    return 0
+
+
+if __name__ == '__main__':
+    sys.exit(main())
@@ -0,0 +1,116 @@
+"""Tests of the asyncio gRPC client"""
+
+import asyncio
+from concurrent import futures
+
+import grpc
+import pytest
+from google.protobuf import empty_pb2
+
+from iroha import Iroha, IrohaCrypto, NoAccountError, endpoint_pb2, endpoint_pb2_grpc, qry_responses_pb2
+from iroha.aio import IrohaGrpcAsync
+from iroha.testing.mock_node import ADMIN_PRIVATE_KEY, MockIrohaNode
+
+COMMITTED_HISTORY = ('ENOUGH_SIGNATURES_COLLECTED', 'STATEFUL_VALIDATION_SUCCESS', 'COMMITTED')
+
+
+class CommittingPeer(endpoint_pb2_grpc.CommandService_v1Servicer, endpoint_pb2_grpc.QueryService_v1Servicer):
+    """
+    Peer committing every received transaction and answering queries with the requested account
+    """
+
+    def __init__(self):
+        self.received = []
+
+    def Torii(self, request, context):
+        self.received.append(request)
+        return empty_pb2.Empty()
+
+    def Status(self, request, context):
+        return endpoint_pb2.ToriiResponse(tx_status=endpoint_pb2.COMMITTED, tx_hash=request.tx_hash)
+
+    def StatusStream(self, request, context):
+        for status in COMMITTED_HISTORY:
+            yield endpoint_pb2.ToriiResponse(tx_status=endpoint_pb2.TxStatus.Value(status), tx_hash=request.tx_hash)
+
+    def Find(self, request, context):
+        response = qry_responses_pb2.QueryResponse()
+        response.account_response.account.account_id = request.payload.get_account.account_id
+        return response
+
+
+@pytest.fixture
+def peer():
+    servicer = CommittingPeer()
+    server = grpc.server(futures.ThreadPoolExecutor(max_workers=4))
+    endpoint_pb2_grpc.add_CommandService_v1Servicer_to_server(servicer, server)
+    endpoint_pb2_grpc.add_QueryService_v1Servicer_to_server(servicer, server)
+    servicer.address = '127.0.0.1:{}'.format(server.add_insecure_port('127.0.0.1:0'))
+    server.start()
+    yield servicer
+    server.stop(None)
+
+
+@pytest.fixture
+def node():
+    with MockIrohaNode() as mock_node:
+        yield mock_node
+
+
+@pytest.fixture
+def iroha():
+    return Iroha('admin@test')
+
+
+def test_send_status_and_query(peer, iroha):
+    tx = IrohaCrypto.sign_transaction(
+        iroha.transaction([Iroha.command('CreateDomain', domain_id='domain', default_role='user')]),
+        ADMIN_PRIVATE_KEY)
+    query = IrohaCrypto.sign_query(iroha.query('GetAccount', account_id='admin@test'), ADMIN_PRIVATE_KEY)
+
+    async def round_trip():
+        async with IrohaGrpcAsync(peer.address, timeout=5) as net:
+            await net.send_tx(tx)
+            statuses = [status async for status, _, _ in net.tx_status_stream(tx)]
+            status = await net.tx_status(tx)
+            response = await net.send_query(query)
+        return statuses, status, response
+
+    statuses, status, response = asyncio.run(round_trip())
+    assert peer.received == [tx]
+    assert statuses == list(COMMITTED_HISTORY)
+    assert status[0] == 'COMMITTED'
+    assert response.account_response.account.account_id == 'admin@test'
+
+
+def test_send_status_and_query_round_trip(node, iroha):
+    tx = IrohaCrypto.sign_transaction(
+        iroha.transaction([Iroha.command('AddAssetQuantity', asset_id='coin#test', amount='1.50')]),
+        ADMIN_PRIVATE_KEY)
+    query = IrohaCrypto.sign_query(iroha.query('GetAccountAssets', account_id='admin@test'), ADMIN_PRIVATE_KEY)
+
+    async def round_trip():
+        async with IrohaGrpcAsync(node.address, timeout=5) as net:
+            await net.send_tx(tx)
+            statuses = [status async for status, _, _ in net.tx_status_stream(tx)]
+            status = await net.tx_status(tx)
+            assets = await net.send_query(query, unwrap=True)
+            health = await net.wait_until_ready(min_height=2, timeout=5)
+        return statuses, status, assets, health
+
+    statuses, status, assets, health = asyncio.run(round_trip())
+    assert statuses[-1] == 'COMMITTED'
+    assert status[0] == 'COMMITTED'
+    assert [(asset.asset_id, asset.balance) for asset in assets.account_assets] == [('coin#test', '1.50')]
+    assert health.last_block_height == 2
+
+
+def test_query_error_is_raised(node, iroha):
+    query = IrohaCrypto.sign_query(iroha.query('GetAccount', account_id='bob@test'), ADMIN_PRIVATE_KEY)
+
+    async def send():
+        async with IrohaGrpcAsync(node.address, timeout=5) as net:
+            return await net.send_query(query, unwrap=True)
+
+    with pytest.raises(NoAccountError):
+        asyncio.run(send())
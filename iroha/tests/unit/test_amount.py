@@ -0,0 +1,50 @@
+"""Tests of the precision-aware Amount type"""
+
+import decimal
+
+import pytest
+
+from iroha import Amount, ValidationError, commands
+from iroha.amount import MAX_UNITS
+
+
+def test_amount_formats_canonically():
+    assert str(Amount('1.5', 2)) == '1.50'
+    assert str(Amount('0.05')) == '0.05'
+    assert str(Amount(3, 4)) == '3.0000'
+    assert str(Amount(decimal.Decimal('12.340'))) == '12.340'
+    assert Amount('1.5', 2).units == 150
+    assert repr(Amount.from_units(1, 3)) == "Amount('0.001')"
+
+
+@pytest.mark.parametrize('create', [
+    lambda: Amount('1.255', 2),
+    lambda: Amount('-1'),
+    lambda: Amount('1e3'),
+    lambda: Amount(1.5),
+    lambda: Amount('1', 256),
+    lambda: Amount(MAX_UNITS + 1),
+    lambda: Amount.from_units(MAX_UNITS, 2) + Amount('0.01'),
+    lambda: Amount('1') - Amount('1.01'),
+])
+def test_invalid_amounts_are_rejected(create):
+    with pytest.raises(ValidationError):
+        create()
+
+
+def test_amount_arithmetic_keeps_precision():
+    total = Amount('1.5', 2) + Amount('2')
+    assert str(total) == '3.50'
+    assert Amount('3.50') - Amount('1.25') == decimal.Decimal('2.25')
+    assert Amount('1.50') == Amount('1.5')
+    assert Amount(0, 2) < Amount('0.01')
+    assert not Amount(0, 2)
+    assert str(Amount.from_units(MAX_UNITS, 0)) == str(MAX_UNITS)
+
+
+def test_builders_accept_amounts():
+    command = commands.AddAssetQuantity('coin#test', Amount('2.5', 2)).to_proto()
+    assert command.add_asset_quantity.amount == '2.50'
+    with pytest.raises(ValidationError) as error:
+        commands.SubtractAssetQuantity('coin#test', 1.5)
+    assert error.value.field == 'amount'
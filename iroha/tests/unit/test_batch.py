@@ -0,0 +1,79 @@
+"""Tests of transaction batches"""
+
+import pytest
+
+from iroha import BatchError, Iroha, IrohaCrypto, ed25519_sha2
+from iroha.batch import Batch
+from iroha.testing.mock_node import ADMIN_PRIVATE_KEY, MockIrohaNode
+
+SECOND_PRIVATE_KEY = 'cc5013e43918bd0e5c4d800416c88bed77892ff077929162bb03ead40a745e88'
+SHA2_PRIVATE_KEY = ed25519_sha2.SigningKey(
+    bytes.fromhex('99fe8969acdafb09bfdd0046370e2fa2580b0c2591a2363625250da14d771b63'))
+
+
+@pytest.fixture
+def transactions():
+    iroha = Iroha('admin@test')
+    return [iroha.transaction([Iroha.command('AddAssetQuantity', asset_id='coin#test', amount=amount)])
+            for amount in ('1', '2')]
+
+
+def test_batch_ties_copies_of_transactions(transactions):
+    batch = Batch(transactions, atomic=False)
+    assert not batch.is_atomic
+    assert not transactions[0].payload.HasField('batch')
+    assert batch.reduced_hashes == [IrohaCrypto.reduced_hash(tx).decode() for tx in transactions]
+    assert all(list(tx.payload.batch.reduced_hashes) == batch.reduced_hashes for tx in batch)
+    assert batch.problems() == []
+
+
+def test_signed_transactions_are_rejected(transactions):
+    IrohaCrypto.sign_transaction(transactions[1], ADMIN_PRIVATE_KEY)
+    with pytest.raises(BatchError) as error:
+        Batch(transactions)
+    assert error.value.problems == ['transaction 1 is already signed']
+
+
+def test_selective_signing_and_requirements(transactions):
+    batch = Batch(transactions)
+    admin_key = IrohaCrypto.derive_public_key(ADMIN_PRIVATE_KEY).decode()
+    assert batch.sign(ADMIN_PRIVATE_KEY, indexes=[1]) == [1]
+    assert batch.sign(ADMIN_PRIVATE_KEY) == [0]
+    assert batch.sign(ADMIN_PRIVATE_KEY, creator='bob@test') == []
+    requirements = batch.signature_requirements({'admin@test': [admin_key, 'ab' * 32]})
+    assert [requirement.missing_keys for requirement in requirements] == [['ab' * 32]] * 2
+    assert batch.is_fully_signed
+    restored = Batch.from_transactions(list(batch))
+    assert restored.hashes == batch.hashes
+
+
+def test_sha2_signatures_are_recognized(transactions):
+    batch = Batch(transactions)
+    assert batch.sign(SHA2_PRIVATE_KEY, ADMIN_PRIVATE_KEY) == [0, 1]
+    assert batch.problems() == []
+    sha2_key = IrohaCrypto.derive_public_key(SHA2_PRIVATE_KEY)
+    admin_key = IrohaCrypto.derive_public_key(ADMIN_PRIVATE_KEY).decode()
+    requirements = batch.signature_requirements()
+    assert [requirement.signed_keys for requirement in requirements] == [[sha2_key, admin_key]] * 2
+
+
+def test_tampering_is_detected(transactions):
+    batch = Batch(transactions)
+    batch.sign(ADMIN_PRIVATE_KEY, SECOND_PRIVATE_KEY)
+    batch.transactions[0].payload.reduced_payload.quorum = 2
+    with pytest.raises(BatchError) as error:
+        batch.check()
+    assert 'reduced hash of transaction 0 does not match the batch' in error.value.problems
+    with pytest.raises(BatchError):
+        batch.sign(ADMIN_PRIVATE_KEY)
+
+
+def test_batch_is_submitted_and_tracked(transactions):
+    batch = Batch(transactions)
+    batch.sign(ADMIN_PRIVATE_KEY)
+    with MockIrohaNode() as node:
+        net = node.client()
+        results = batch.submit_await(net, timeout=5)
+        assert list(results) == batch.hashes
+        assert all(result.is_committed for result in results.values())
+        assert [status[0] for status in batch.statuses(net).values()] == ['COMMITTED'] * 2
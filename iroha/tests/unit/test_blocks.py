@@ -0,0 +1,132 @@
+"""Tests of blocks subscription and GetBlock helpers"""
+
+import itertools
+
+import grpc
+import pytest
+
+from iroha import Iroha, IrohaCrypto, RetryPolicy, qry_responses_pb2
+from iroha.blocks import (BlockRangeFetcher, BlockSubscription, FileCheckpointStore, MemoryCheckpointStore,
+                          block_height, get_block)
+from iroha.testing.faults import Delay, Fail, FaultInjectingGrpc, PassThrough, Respond, error_response
+from iroha.testing.mock_node import ADMIN_PRIVATE_KEY, MockIrohaNode
+
+
+@pytest.fixture
+def node():
+    with MockIrohaNode() as mock_node:
+        yield mock_node
+
+
+@pytest.fixture
+def iroha():
+    return Iroha('admin@test')
+
+
+def commit_blocks(node, iroha, count):
+    for amount in range(1, count + 1):
+        tx = iroha.transaction([Iroha.command('AddAssetQuantity', asset_id='coin#test', amount=str(amount))])
+        node.client().send_tx_await(IrohaCrypto.sign_transaction(tx, ADMIN_PRIVATE_KEY), timeout=5)
+
+
+def block_responses(node, *heights):
+    responses = []
+    for height in heights:
+        response = qry_responses_pb2.BlockQueryResponse()
+        response.block_response.block.CopyFrom(node.blocks[height - 1])
+        responses.append(response)
+    return responses
+
+
+def test_get_block(node, iroha):
+    net = node.client()
+    assert block_height(get_block(iroha, net, ADMIN_PRIVATE_KEY, 1)) == 1
+    assert get_block(iroha, net, ADMIN_PRIVATE_KEY, 2) is None
+
+
+def test_file_checkpoint_store(tmp_path):
+    path = str(tmp_path / 'checkpoint')
+    assert FileCheckpointStore(path).load() is None
+    FileCheckpointStore(path).save(3)
+    assert FileCheckpointStore(path).load() == 3
+
+
+def test_gap_in_stream_is_backfilled(node, iroha):
+    commit_blocks(node, iroha, 3)
+    net = FaultInjectingGrpc(node.address)
+    net.inject('FetchCommits', Respond(block_responses(node, 2, 4)))
+    subscription = BlockSubscription(iroha, net, ADMIN_PRIVATE_KEY)
+    assert [block_height(block) for block in itertools.islice(subscription, 3)] == [2, 3, 4]
+    assert net.call_count('Find') == 1
+
+
+def test_subscription_reconnects_after_stream_error(node, iroha):
+    commit_blocks(node, iroha, 2)
+    net = FaultInjectingGrpc(node.address)
+    net.inject('FetchCommits', Respond(block_responses(node, 2), code=grpc.StatusCode.UNAVAILABLE))
+    delays = []
+    store = MemoryCheckpointStore()
+    subscription = BlockSubscription(iroha, net, ADMIN_PRIVATE_KEY, checkpoint_store=store,
+                                     retry_policy=RetryPolicy(jitter=0, sleep=delays.append))
+    assert [block_height(block) for block in itertools.islice(subscription, 2)] == [2, 3]
+    assert len(delays) == 1
+    assert store.load() == 2
+    assert net.call_count('FetchCommits') == 1
+    assert net.call_count('Find') == 1
+
+
+def test_reconnection_attempts_are_limited(node, iroha):
+    net = FaultInjectingGrpc(node.address)
+    net.always('FetchCommits', Fail(grpc.StatusCode.UNAVAILABLE))
+    delays = []
+    subscription = BlockSubscription(iroha, net, ADMIN_PRIVATE_KEY,
+                                     retry_policy=RetryPolicy(max_attempts=3, jitter=0, sleep=delays.append))
+    with pytest.raises(grpc.RpcError) as error:
+        next(iter(subscription))
+    assert error.value.code() == grpc.StatusCode.UNAVAILABLE
+    assert net.call_count('FetchCommits') == 3
+    assert len(delays) == 2
+
+
+def test_subscription_resumes_from_checkpoint(node, iroha, tmp_path):
+    commit_blocks(node, iroha, 3)
+    store = FileCheckpointStore(str(tmp_path / 'checkpoint'))
+    store.save(2)
+    net = FaultInjectingGrpc(node.address)
+    subscription = BlockSubscription(iroha, net, ADMIN_PRIVATE_KEY, start_height=1, checkpoint_store=store)
+    heights = []
+    for block in subscription:
+        heights.append(block_height(block))
+        if block_height(block) == 4:
+            subscription.close()
+    assert heights == [3, 4]
+    assert store.load() == 4
+    assert net.call_count('FetchCommits') == 0
+
+
+def test_range_is_delivered_in_height_order(node, iroha):
+    commit_blocks(node, iroha, 4)
+    net = FaultInjectingGrpc(node.address)
+    net.inject('Find', Delay(0.2))
+    fetcher = BlockRangeFetcher(iroha, net, ADMIN_PRIVATE_KEY, concurrency=3)
+    assert [block_height(block) for block in fetcher.blocks()] == [1, 2, 3, 4, 5]
+    assert [block_height(block) for block in fetcher.blocks(first_height=2, last_height=4)] == [2, 3, 4]
+
+
+def test_range_stops_at_the_top_of_the_chain(node, iroha):
+    fetcher = BlockRangeFetcher(iroha, FaultInjectingGrpc(node.address), ADMIN_PRIVATE_KEY, concurrency=2)
+    assert [block_height(block) for block in fetcher.blocks()] == [1]
+    assert list(fetcher.blocks(first_height=3)) == []
+
+
+def test_range_hands_off_to_subscription(node, iroha):
+    commit_blocks(node, iroha, 4)
+    net = FaultInjectingGrpc(node.address)
+    # the chain looks 4 blocks high to the fetcher, the 5th one is found by the subscription
+    net.inject('Find', *([PassThrough()] * 4 + [error_response('STATEFUL_INVALID', 'Invalid height 5', 3)]))
+    store = MemoryCheckpointStore(1)
+    fetcher = BlockRangeFetcher(iroha, net, ADMIN_PRIVATE_KEY, concurrency=1)
+    blocks = fetcher.blocks(follow=True, checkpoint_store=store)
+    assert [block_height(block) for block in itertools.islice(blocks, 5)] == [1, 2, 3, 4, 5]
+    assert store.load() == 4
+    assert net.call_count('FetchCommits') == 0
@@ -0,0 +1,55 @@
+"""Tests of typed command builders"""
+
+import decimal
+
+import pytest
+
+from iroha import AccountId, Iroha, ValidationError, commands, primitive_pb2
+from iroha.commands import CommandBuilder
+
+
+def test_builder_matches_factory_method():
+    built = commands.TransferAsset('admin@test', 'test@test', 'coin#test', decimal.Decimal('2.50'), 'rent')
+    expected = Iroha.command('TransferAsset', src_account_id='admin@test', dest_account_id='test@test',
+                             asset_id='coin#test', description='rent', amount='2.50')
+    assert Iroha.command(built) == expected
+    tx = Iroha('admin@test').transaction([built])
+    assert tx.payload.reduced_payload.commands[0] == expected
+
+
+@pytest.mark.parametrize('builder', [
+    commands.AddPeer('127.0.0.1:10001', 'ab' * 32, syncing_peer=True),
+    commands.AddPeer('127.0.0.1:10001', 'ab' * 32, tls_certificate=''),
+    commands.CompareAndSetAccountDetail('admin@test', 'age', '18'),
+    commands.CompareAndSetAccountDetail('admin@test', 'age', '18', old_value=''),
+    commands.CreateRole('reader', ['can_get_my_account', primitive_pb2.can_get_my_signatories]),
+    commands.GrantPermission('test@test', 'can_set_my_quorum'),
+    commands.CallEngine('admin@test', '6060', callee='aa' * 20),
+])
+def test_proto_round_trip_keeps_oneof_presence(builder):
+    assert CommandBuilder.from_proto(builder.to_proto()) == builder
+
+
+@pytest.mark.parametrize('build', [
+    lambda: commands.AddAssetQuantity('coin', '1'),
+    lambda: commands.AddAssetQuantity('coin#test', '-1'),
+    lambda: commands.CreateAsset('coin', 'test', precision=256),
+    lambda: commands.SetAccountQuorum('admin@test', 0),
+    lambda: commands.CreateAccount('Admin', 'test', 'ab' * 32),
+    lambda: commands.CreateRole('reader', ['can_fly']),
+    lambda: commands.SetAccountDetail('admin@test', 'key', 42),
+])
+def test_invalid_arguments_are_rejected(build):
+    with pytest.raises(ValidationError):
+        build()
+
+
+@pytest.mark.parametrize('src_account_id, dest_account_id', [
+    ('admin@test', 'admin@test'),
+    (AccountId.parse('admin@test'), 'admin@test'),
+    ('admin@test', AccountId.parse('admin@test')),
+])
+def test_transfer_to_the_same_account_is_rejected(src_account_id, dest_account_id):
+    with pytest.raises(ValidationError) as error:
+        commands.TransferAsset(src_account_id, dest_account_id, 'coin#test', '1')
+    assert error.value.field == 'dest_account_id'
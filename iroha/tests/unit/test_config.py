@@ -0,0 +1,123 @@
+"""Tests of the daemon config model"""
+
+import json
+import os
+
+import pytest
+
+from iroha import ConfigError
+from iroha.config import ConfigProblem, DatabaseConfig, IrohaConfig, WARNING, main
+
+CONFIG_PATH = os.path.join(os.path.dirname(__file__), '..', '..', '..', 'docker', 'iroha', 'config.docker')
+
+
+def minimal_config(**values):
+    data = {
+        'torii_port': 50051,
+        'internal_port': 10001,
+        'database': {'host': 'localhost', 'port': 5432, 'user': 'postgres', 'password': 'secret'},
+        'max_proposal_size': 10,
+        'proposal_creation_timeout': 5000,
+        'vote_delay': 5000,
+    }
+    data.update(values)
+    return IrohaConfig.from_dict(data)
+
+
+def test_example_config_has_warnings_only():
+    config = IrohaConfig.load(CONFIG_PATH)
+    assert config.torii_port == 50051
+    assert config.mst_enable is True
+    assert config.problems() == [
+        ConfigProblem('load_delay', 'load_delay is not used by Iroha 1.x and is ignored', WARNING),
+        ConfigProblem('proposal_delay', 'deprecated, use proposal_creation_timeout', WARNING),
+    ]
+    config.check()
+
+
+def test_save_and_load_round_trip(tmp_path):
+    config = IrohaConfig.load(CONFIG_PATH)
+    path = str(tmp_path / 'config.docker')
+    config.save(path)
+    assert IrohaConfig.load(path) == config
+    assert list(IrohaConfig.load(path).to_dict()) == list(config.to_dict())
+    with open(CONFIG_PATH) as config_file:
+        assert json.loads((tmp_path / 'config.docker').read_text()) == json.load(config_file)
+
+
+def test_database_section_keys_with_spaces():
+    config = minimal_config(database={'host': 'pg', 'port': 5432, 'user': 'u', 'password': 'p',
+                                      'working database': 'iroha_data'})
+    assert config.database == DatabaseConfig(host='pg', port=5432, user='u', password='p',
+                                             working_database='iroha_data')
+    assert config.to_dict()['database']['working database'] == 'iroha_data'
+    assert config.problems() == []
+
+
+def test_inter_peer_tls_section():
+    config = minimal_config(inter_peer_tls={'key_pair_path': '/keys/node0', 'peer_certificates': {'from_wsv': {}}})
+    assert config.inter_peer_tls.key_pair_path == '/keys/node0'
+    assert config.problems() == []
+
+
+def test_proposal_delay_is_accepted_as_deprecated_key():
+    config = minimal_config()
+    config.proposal_creation_timeout = None
+    assert config.errors() == [ConfigProblem('proposal_creation_timeout', 'required key is missing')]
+    config.proposal_delay = 5000
+    assert config.problems() == [ConfigProblem('proposal_delay', 'deprecated, use proposal_creation_timeout',
+                                               WARNING)]
+
+
+def test_unknown_keys_are_reported_with_suggestions():
+    config = minimal_config(mst_enabel=True)
+    assert config.problems() == [ConfigProblem('mst_enabel', 'unknown key, did you mean mst_enable?')]
+    assert config.to_dict()['mst_enabel'] is True
+    with pytest.raises(ConfigError):
+        config.check()
+
+
+def test_invalid_values_and_combinations():
+    config = minimal_config(torii_port='50051', vote_delay=0, pg_opt='host=pg port=5432 user=u password=p',
+                            utility_service={'ip': '0.0.0.0', 'port': 10001},
+                            torii_tls_params={'port': 70000, 'key_pair_path': '/keys/torii'})
+    messages = [str(problem) for problem in config.problems()]
+    assert messages == [
+        'error torii_port: integer expected, got "50051"',
+        'error torii_tls_params.port: port 70000 is out of range [1, 65535]',
+        'error vote_delay: positive value expected, got 0',
+        'error pg_opt: pg_opt and database must not be set together',
+        'error utility_service.port: port 10001 is already used by internal_port',
+    ]
+
+
+def test_missing_database_and_malformed_pg_opt():
+    config = minimal_config()
+    config.database = None
+    assert config.errors() == [ConfigProblem('database', 'either database or pg_opt is required')]
+    config.pg_opt = 'host=pg port=5432'
+    assert config.errors() == [ConfigProblem('pg_opt', 'missing user, password')]
+
+
+def test_diff():
+    config = minimal_config()
+    other = minimal_config(vote_delay=3000, mst_enable=True)
+    other.database.port = 5433
+    assert config.diff(other) == [('database.port', 5432, 5433), ('vote_delay', 5000, 3000),
+                                  ('mst_enable', None, True)]
+    assert config.diff(minimal_config()) == []
+
+
+def test_malformed_json_raises():
+    with pytest.raises(ConfigError):
+        IrohaConfig.from_json('{"torii_port": ')
+    with pytest.raises(ConfigError):
+        IrohaConfig.from_json('[]')
+
+
+def test_command_line(tmp_path, capsys):
+    path = str(tmp_path / 'config.docker')
+    minimal_config(torii_port=10001).save(path)
+    assert main(['check', path]) == 1
+    assert 'port 10001 is already used by torii_port' in capsys.readouterr().out
+    assert main(['diff', CONFIG_PATH, CONFIG_PATH]) == 0
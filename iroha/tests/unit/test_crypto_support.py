@@ -18,3 +18,11 @@ def test_create_signature(crypto_data):
     else:
         validate = IrohaCrypto.is_signature_valid(crypto_data.message, signature)
         assert validate
+
+
+def test_any_signature_is_validated(crypto_data):
+    """Checking the key type is recognized by the public key"""
+    signature = IrohaCrypto._signature(crypto_data.message, crypto_data.private_key)
+    assert IrohaCrypto.is_any_signature_valid(crypto_data.message, signature)
+    signature.signature = signature.signature[:-2] + ('00' if signature.signature[-2:] != '00' else '01')
+    assert not IrohaCrypto.is_any_signature_valid(crypto_data.message, signature)
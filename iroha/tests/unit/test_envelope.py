@@ -0,0 +1,73 @@
+"""Tests of offline signing envelopes"""
+
+import pytest
+
+from iroha import EnvelopeError, Iroha, IrohaCrypto
+from iroha.batch import Batch
+from iroha.envelope import Envelope, main
+from iroha.testing.mock_node import ADMIN_PRIVATE_KEY
+
+SECOND_PRIVATE_KEY = 'cc5013e43918bd0e5c4d800416c88bed77892ff077929162bb03ead40a745e88'
+
+
+@pytest.fixture
+def transaction():
+    return Iroha('admin@test').transaction(
+        [Iroha.command('AddAssetQuantity', asset_id='coin#test', amount='1.5')], quorum=2)
+
+
+@pytest.mark.parametrize('encoding', ['json', 'base64', 'qr'])
+def test_envelope_round_trips(transaction, encoding):
+    envelope = Envelope([transaction]).sign(ADMIN_PRIVATE_KEY)
+    decoded = Envelope.decode(envelope.encode(encoding))
+    assert decoded.hashes == [IrohaCrypto.hash(transaction).hex()]
+    assert decoded.transactions() == envelope.transactions()
+    assert 'AddAssetQuantity' in decoded.summary()
+
+
+def test_signatures_are_merged(transaction):
+    exported = Envelope([transaction]).to_json()
+    first = Envelope.from_json(exported).sign(ADMIN_PRIVATE_KEY)
+    second = Envelope.from_json(exported).sign(SECOND_PRIVATE_KEY, ADMIN_PRIVATE_KEY)
+    merged = first.merge(second)
+    assert len(merged.signatures()[0]) == 2
+    assert merged.transactions()[0].payload == transaction.payload
+
+
+def test_tampering_is_detected(transaction):
+    data = Envelope([transaction]).to_dict()
+    other = Iroha('admin@test').transaction([Iroha.command('AddAssetQuantity', asset_id='coin#test', amount='100')])
+    data['transactions'][0]['transaction'] = Envelope([other]).to_dict()['transactions'][0]['transaction']
+    with pytest.raises(EnvelopeError):
+        Envelope.from_dict(data)
+    signed = Envelope([transaction]).sign(ADMIN_PRIVATE_KEY).to_dict()
+    signed['transactions'][0]['signatures'][0]['signature'] = '00' * 64
+    with pytest.raises(EnvelopeError):
+        Envelope.from_dict(signed)
+
+
+def test_qr_chunks_are_checked(transaction):
+    chunks = Envelope([transaction]).to_qr_chunks(chunk_size=100)
+    assert len(chunks) > 1
+    assert Envelope.from_qr_chunks(reversed(chunks)).hashes == Envelope([transaction]).hashes
+    with pytest.raises(EnvelopeError):
+        Envelope.from_qr_chunks(chunks[1:])
+
+
+def test_batch_envelope_cli(tmp_path, transaction, capsys):
+    iroha = Iroha('admin@test')
+    batch = Batch([transaction, iroha.transaction([Iroha.command('SetAccountQuorum', account_id='admin@test',
+                                                                 quorum=1)])])
+    paths = []
+    for index, tx in enumerate(batch):
+        path = tmp_path / 'tx{}.bin'.format(index)
+        path.write_bytes(tx.SerializeToString())
+        paths.append(str(path))
+    envelope_path = str(tmp_path / 'batch.txt')
+    assert main(['export', *paths, '--encoding', 'qr', '-o', envelope_path]) == 0
+    assert main(['sign', envelope_path, '--key', ADMIN_PRIVATE_KEY, '-o', envelope_path]) == 0
+    assert main(['inspect', envelope_path]) == 0
+    assert 'ATOMIC batch of 2 transactions' in capsys.readouterr().out
+    signed = Envelope.decode((tmp_path / 'batch.txt').read_text())
+    assert signed.kind == 'batch'
+    assert all(len(keys) == 1 for keys in signed.signatures())
@@ -0,0 +1,55 @@
+"""Tests of the fault injecting test transport"""
+
+import grpc
+import pytest
+
+from iroha import Iroha, IrohaCrypto, MstExpiredError, NoAccountError, RetryPolicy
+from iroha.testing.faults import Delay, Fail, FaultInjectingGrpc, empty_response, error_response, tx_status, \
+    tx_statuses
+from iroha.testing.mock_node import ADMIN_PRIVATE_KEY
+
+
+@pytest.fixture
+def tx():
+    iroha = Iroha('admin@test')
+    return IrohaCrypto.sign_transaction(
+        iroha.transaction([Iroha.command('CreateDomain', domain_id='d', default_role='user')]), ADMIN_PRIVATE_KEY)
+
+
+def test_unavailable_send_is_retried_when_not_received(tx):
+    policy = RetryPolicy(max_attempts=3, sleep=lambda delay: None)
+    net = FaultInjectingGrpc(retry_policy=policy)
+    tx_hash = IrohaCrypto.hash(tx)
+    net.inject('Torii', Fail(grpc.StatusCode.UNAVAILABLE), empty_response())
+    net.always('Status', tx_status(tx_hash, 'NOT_RECEIVED'))
+    net.send_tx(tx)
+    assert [method for method, _ in net.calls] == ['Torii', 'Status', 'Torii']
+
+
+def test_latency_above_timeout_is_deadline_exceeded(tx):
+    delays = []
+    net = FaultInjectingGrpc(sleep=delays.append)
+    net.inject('Torii', Delay(2, then=empty_response()))
+    with pytest.raises(grpc.RpcError) as error:
+        net.send_tx(tx, timeout=1)
+    assert error.value.code() == grpc.StatusCode.DEADLINE_EXCEEDED
+    assert delays == [1]
+
+
+def test_custom_status_sequence(tx):
+    net = FaultInjectingGrpc()
+    net.inject('Torii', empty_response())
+    net.inject('StatusStream', tx_statuses(IrohaCrypto.hash(tx), 'ENOUGH_SIGNATURES_COLLECTED',
+                                           'MST_PENDING', 'MST_EXPIRED'))
+    with pytest.raises(MstExpiredError) as error:
+        net.send_tx_await(tx, timeout=5)
+    assert error.value.result.history == ['ENOUGH_SIGNATURES_COLLECTED', 'MST_PENDING', 'MST_EXPIRED']
+
+
+def test_error_response_query():
+    net = FaultInjectingGrpc()
+    net.inject('Find', error_response('NO_ACCOUNT', 'no account bob@test'))
+    query = IrohaCrypto.sign_query(Iroha('admin@test').query('GetAccount', account_id='bob@test'), ADMIN_PRIVATE_KEY)
+    with pytest.raises(NoAccountError):
+        net.send_query(query, unwrap=True)
+    assert net.pending_steps('Find') == 0
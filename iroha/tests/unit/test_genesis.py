@@ -0,0 +1,81 @@
+"""Tests of the genesis block builder"""
+
+import os
+
+import pytest
+
+from iroha import GenesisError, Iroha, IrohaCrypto, protojson
+from iroha.genesis import GenesisBuilder, example_genesis
+from iroha.testing.mock_node import ADMIN_PRIVATE_KEY, MockIrohaNode
+
+GENESIS_PATH = os.path.join(os.path.dirname(__file__), '..', '..', '..', 'docker', 'iroha', 'genesis.block')
+PEER_KEY = 'bddd58404d1315e0eb27902c5d7c8eb0602c16238f005773df406bc191308929'
+ADMIN_PUBLIC_KEY = '313a07e6384776ed95447710d15e59148473ccfc052a681317a72a69f2a49910'
+TEST_PUBLIC_KEY = '716fe505f69f18511a1b083915aa9ff73ef36e6688199f3959750db38b8f4bfc'
+
+
+def example_builder():
+    return GenesisBuilder() \
+        .add_peer('127.0.0.1:10001', PEER_KEY) \
+        .add_role('admin', ['can_add_peer', 'can_add_signatory', 'can_create_account', 'can_create_domain',
+                            'can_get_all_acc_ast', 'can_get_all_acc_ast_txs', 'can_get_all_acc_detail',
+                            'can_get_all_acc_txs', 'can_get_all_accounts', 'can_get_all_signatories',
+                            'can_get_all_txs', 'can_get_blocks', 'can_get_roles', 'can_read_assets',
+                            'can_remove_signatory', 'can_set_quorum']) \
+        .add_role('user', ['can_add_signatory', 'can_get_my_acc_ast', 'can_get_my_acc_ast_txs',
+                           'can_get_my_acc_detail', 'can_get_my_acc_txs', 'can_get_my_account',
+                           'can_get_my_signatories', 'can_get_my_txs', 'can_grant_can_add_my_signatory',
+                           'can_grant_can_remove_my_signatory', 'can_grant_can_set_my_account_detail',
+                           'can_grant_can_set_my_quorum', 'can_grant_can_transfer_my_assets', 'can_receive',
+                           'can_remove_signatory', 'can_set_quorum', 'can_transfer']) \
+        .add_role('money_creator', ['can_add_asset_qty', 'can_create_asset', 'can_receive', 'can_transfer']) \
+        .add_domain('test', 'user') \
+        .add_asset('coin#test', 2) \
+        .add_account('admin@test', ADMIN_PUBLIC_KEY, roles=['admin', 'money_creator']) \
+        .add_account('test@test', TEST_PUBLIC_KEY)
+
+
+def test_example_genesis_block_is_reproduced(tmp_path):
+    path = str(tmp_path / 'genesis.block')
+    example_builder().save(path)
+    assert protojson.load(path) == protojson.load(GENESIS_PATH)
+
+
+def test_example_genesis_matches_example_block_and_accepts_more_peers():
+    assert example_genesis([('127.0.0.1:10001', PEER_KEY)], ADMIN_PUBLIC_KEY, TEST_PUBLIC_KEY).build() \
+        == protojson.load(GENESIS_PATH)
+    builder = example_genesis([('iroha0:10001', PEER_KEY), ('iroha1:10001', TEST_PUBLIC_KEY)],
+                              ADMIN_PUBLIC_KEY, TEST_PUBLIC_KEY)
+    commands = builder.transactions()[0].payload.reduced_payload.commands
+    assert [command.add_peer.peer.address for command in commands[:2]] == ['iroha0:10001', 'iroha1:10001']
+
+
+def test_inconsistencies_are_reported():
+    builder = GenesisBuilder() \
+        .add_peer('127.0.0.1:10001', PEER_KEY, syncing_peer=True) \
+        .add_domain('test', 'user') \
+        .add_asset('coin#other', 2) \
+        .add_account('alice@test', [ADMIN_PUBLIC_KEY], quorum=2, balances={'coin#other': '1.255'})
+    assert builder.problems() == [
+        'at least one peer taking part in consensus is required',
+        'default role user of domain test is not added',
+        'domain other of asset coin#other is not added',
+        'quorum 2 of account alice@test exceeds the number of its public keys',
+        'balance of coin#other on account alice@test: 1.255 has more than 2 digits after the point',
+    ]
+    with pytest.raises(GenesisError):
+        builder.build()
+
+
+def test_seeded_accounts_are_loaded_by_a_node():
+    block = example_builder() \
+        .add_account('alice@test', [TEST_PUBLIC_KEY, ADMIN_PUBLIC_KEY], quorum=2,
+                     details={'age': '18'}, balances={'coin#test': '10.5'}) \
+        .build()
+    assert block.block_v1.payload.tx_number == 2
+    with MockIrohaNode(block) as node:
+        query = IrohaCrypto.sign_query(Iroha('admin@test').query('GetAccountAssets', account_id='alice@test'),
+                                       ADMIN_PRIVATE_KEY)
+        balances = node.client().send_query(query, unwrap=True).account_assets
+        assert [(asset.asset_id, asset.balance) for asset in balances] == [('coin#test', '10.50')]
+        assert node.state.accounts['alice@test'].quorum == 2
@@ -0,0 +1,110 @@
+"""Tests of peer healthcheck and readiness waiting"""
+
+import asyncio
+from concurrent import futures
+
+import grpc
+import pytest
+
+from iroha import IrohaGrpc, endpoint_pb2_grpc, qry_responses_pb2
+from iroha.aio import IrohaGrpcAsync
+from iroha.testing.mock_node import MockIrohaNode
+
+
+class ScriptedPeer(endpoint_pb2_grpc.QueryService_v1Servicer):
+    """
+    Peer answering Healthcheck calls with scripted data or errors, the last answer is repeated
+    """
+
+    def __init__(self, *answers):
+        self.answers = list(answers)
+        self.calls = 0
+
+    def Healthcheck(self, request, context):
+        self.calls += 1
+        answer = self.answers.pop(0) if len(self.answers) > 1 else self.answers[0]
+        if isinstance(answer, grpc.StatusCode):
+            context.abort(answer, 'scripted failure')
+        return answer
+
+
+@pytest.fixture
+def node():
+    with MockIrohaNode() as mock_node:
+        yield mock_node
+
+
+@pytest.fixture
+def serve():
+    servers = []
+
+    def start(servicer):
+        server = grpc.server(futures.ThreadPoolExecutor(max_workers=2))
+        endpoint_pb2_grpc.add_QueryService_v1Servicer_to_server(servicer, server)
+        address = '127.0.0.1:{}'.format(server.add_insecure_port('127.0.0.1:0'))
+        server.start()
+        servers.append(server)
+        return address
+
+    yield start
+    for server in servers:
+        server.stop(None)
+
+
+def healthcheck_data(**fields):
+    return qry_responses_pb2.HealthcheckData(**fields)
+
+
+def test_is_ready():
+    assert not IrohaGrpc.is_ready(healthcheck_data())
+    assert not IrohaGrpc.is_ready(healthcheck_data(is_healthy=False))
+    assert not IrohaGrpc.is_ready(healthcheck_data(is_healthy=True, is_syncing=True))
+    assert IrohaGrpc.is_ready(healthcheck_data(is_healthy=True, is_syncing=False))
+    assert not IrohaGrpc.is_ready(healthcheck_data(is_healthy=True, last_block_height=2), min_height=3)
+    assert IrohaGrpc.is_ready(healthcheck_data(is_healthy=True, last_block_height=3), min_height=3)
+
+
+def test_healthcheck(node):
+    data = node.client().healthcheck(timeout=5)
+    assert data.last_block_height == 1
+    assert IrohaGrpc.is_ready(data, min_height=1)
+
+
+def test_wait_until_ready(serve):
+    peer = ScriptedPeer(grpc.StatusCode.UNAVAILABLE,
+                        healthcheck_data(is_healthy=True, is_syncing=True),
+                        healthcheck_data(is_healthy=True, is_syncing=False, last_block_height=4))
+    data = IrohaGrpc(serve(peer), timeout=5).wait_until_ready(min_height=4, timeout=5, poll_interval=0.01)
+    assert data.last_block_height == 4
+    assert peer.calls == 3
+
+
+def test_wait_until_ready_deadline_is_exceeded(serve):
+    net = IrohaGrpc(serve(ScriptedPeer(grpc.StatusCode.UNAVAILABLE)), timeout=5)
+    with pytest.raises(TimeoutError):
+        net.wait_until_ready(timeout=0.2, poll_interval=0.05)
+
+
+def test_wait_until_ready_for_height_deadline_is_exceeded(node):
+    with pytest.raises(TimeoutError):
+        node.client().wait_until_ready(min_height=2, timeout=0.2, poll_interval=0.05)
+
+
+def test_wait_until_ready_raises_permanent_errors(serve):
+    net = IrohaGrpc(serve(ScriptedPeer(grpc.StatusCode.PERMISSION_DENIED)), timeout=5)
+    with pytest.raises(grpc.RpcError) as error:
+        net.wait_until_ready(timeout=5, poll_interval=0.01)
+    assert error.value.code() == grpc.StatusCode.PERMISSION_DENIED
+
+
+def test_async_wait_until_ready(serve):
+    ready = serve(ScriptedPeer(grpc.StatusCode.UNAVAILABLE, healthcheck_data(is_healthy=True)))
+    not_ready = serve(ScriptedPeer(healthcheck_data(is_healthy=True, last_block_height=1)))
+
+    async def wait(address, **kwargs):
+        async with IrohaGrpcAsync(address, timeout=5) as net:
+            return await net.wait_until_ready(poll_interval=0.01, **kwargs)
+
+    assert asyncio.run(wait(ready, timeout=5)).is_healthy
+    with pytest.raises(TimeoutError):
+        asyncio.run(wait(not_ready, min_height=2, timeout=0.2))
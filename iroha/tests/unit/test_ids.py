@@ -0,0 +1,43 @@
+"""Tests of entity id value types"""
+
+import pytest
+
+from iroha import AccountId, AssetId, DomainId, Iroha, RoleId, ValidationError, commands
+
+
+def test_ids_round_trip_strings():
+    account = AccountId.parse('admin@test.example')
+    assert account.name == 'admin'
+    assert account.domain == DomainId('test.example')
+    assert str(account) == 'admin@test.example'
+    assert AssetId.parse('coin#test') == AssetId('coin', 'test')
+    assert str(RoleId('money_creator')) == 'money_creator'
+    assert len({AccountId('a', 'b'), AccountId.parse('a@b'), AssetId('a', 'b')}) == 2
+    assert AccountId('a', 'b') != 'a@b'
+
+
+@pytest.mark.parametrize('parse', [
+    lambda: AccountId.parse('admin#test'),
+    lambda: AccountId.parse('Admin@test'),
+    lambda: AssetId.parse('coin#-test'),
+    lambda: DomainId('te st'),
+    lambda: RoleId(42),
+])
+def test_invalid_ids_are_rejected(parse):
+    with pytest.raises(ValidationError):
+        parse()
+
+
+def test_factory_methods_accept_ids():
+    admin = AccountId.parse('admin@test')
+    iroha = Iroha(admin)
+    tx = iroha.transaction([
+        Iroha.command('TransferAsset', src_account_id=admin, dest_account_id=AccountId('test', 'test'),
+                      asset_id=AssetId('coin', 'test'), amount='1'),
+        commands.AppendRole(admin, RoleId('user')),
+    ])
+    assert tx.payload.reduced_payload.creator_account_id == 'admin@test'
+    assert tx.payload.reduced_payload.commands[0].transfer_asset.dest_account_id == 'test@test'
+    assert tx.payload.reduced_payload.commands[1].append_role.role_name == 'user'
+    query = iroha.query('GetAccountAssets', account_id=admin)
+    assert query.payload.get_account_assets.account_id == 'admin@test'
@@ -0,0 +1,60 @@
+"""Tests of the in-process mock Iroha node"""
+
+import pytest
+
+from iroha import Iroha, IrohaCrypto, NoAccountError, TransactionRejectedError
+from iroha.testing.mock_node import ADMIN_PRIVATE_KEY, MockIrohaNode
+
+
+@pytest.fixture
+def node():
+    with MockIrohaNode() as mock_node:
+        yield mock_node
+
+
+@pytest.fixture
+def iroha():
+    return Iroha('admin@test')
+
+
+def test_transaction_is_committed(node, iroha):
+    net = node.client()
+    tx = IrohaCrypto.sign_transaction(
+        iroha.transaction([Iroha.command('AddAssetQuantity', asset_id='coin#test', amount='1.50')]),
+        ADMIN_PRIVATE_KEY)
+    result = net.send_tx_await(tx, timeout=5)
+    assert result.is_committed
+    assert result.history == ['STATELESS_VALIDATION_SUCCESS', 'ENOUGH_SIGNATURES_COLLECTED',
+                              'STATEFUL_VALIDATION_SUCCESS', 'COMMITTED']
+    assert node.height == 2
+
+    query = IrohaCrypto.sign_query(iroha.query('GetAccountAssets', account_id='admin@test'), ADMIN_PRIVATE_KEY)
+    assets = net.send_query(query, unwrap=True).account_assets
+    assert [(asset.asset_id, asset.balance) for asset in assets] == [('coin#test', '1.50')]
+
+
+def test_multi_signature_transaction_passes_stateless_validation_first(iroha):
+    tx = IrohaCrypto.sign_transaction(
+        iroha.transaction([Iroha.command('AddAssetQuantity', asset_id='coin#test', amount='1')], quorum=2),
+        ADMIN_PRIVATE_KEY)
+    with MockIrohaNode(status_stream_timeout=0.5) as node:
+        net = node.client()
+        net.send_tx(tx)
+        assert [status for status, _, _ in net.tx_status_stream(tx)] == ['STATELESS_VALIDATION_SUCCESS', 'MST_PENDING']
+
+
+def test_failed_command_is_rejected(node, iroha):
+    tx = IrohaCrypto.sign_transaction(
+        iroha.transaction([Iroha.command('TransferAsset', src_account_id='admin@test',
+                                         dest_account_id='test@test', asset_id='coin#test', amount='1')]),
+        ADMIN_PRIVATE_KEY)
+    with pytest.raises(TransactionRejectedError) as error:
+        node.client().send_tx_await(tx, timeout=5)
+    assert error.value.err_or_cmd_name == 'TransferAsset'
+    assert error.value.error_code == 6
+
+
+def test_query_error_response(node, iroha):
+    query = IrohaCrypto.sign_query(iroha.query('GetAccount', account_id='bob@test'), ADMIN_PRIVATE_KEY)
+    with pytest.raises(NoAccountError):
+        node.client().send_query(query, unwrap=True)
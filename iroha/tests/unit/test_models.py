@@ -0,0 +1,80 @@
+"""Tests of Python models of query responses"""
+
+import pytest
+
+from iroha import Amount, Iroha, IrohaCrypto, QueryError, commands, models
+from iroha.qry_responses_pb2 import QueryResponse
+from iroha.testing.mock_node import ADMIN_PRIVATE_KEY
+
+
+def round_trip(response):
+    model = models.from_query_response(response)
+    assert model.to_query_response().SerializeToString() == response.SerializeToString()
+    return model
+
+
+def test_account_details_are_parsed():
+    response = QueryResponse(query_hash='ab')
+    account = response.account_response.account
+    account.account_id = 'alice@test'
+    account.domain_id = 'test'
+    account.quorum = 2
+    account.json_data = '{ "admin@test": {"age": "18"} }'
+    response.account_response.account_roles.extend(['user'])
+    model = round_trip(response)
+    assert model.account.details == {'admin@test': {'age': '18'}}
+    assert model.roles == ['user']
+    model.account.details['admin@test']['age'] = '19'
+    assert model.to_proto().account.json_data == '{"admin@test":{"age":"19"}}'
+
+
+def test_account_assets_page_has_amounts():
+    response = QueryResponse()
+    page = response.account_assets_response
+    page.account_assets.add(asset_id='coin#test', account_id='alice@test', balance='1.50')
+    page.total_number = 2
+    page.next_asset_id = 'gold#test'
+    model = round_trip(response)
+    assert model.account_assets[0].balance == Amount('1.5')
+    assert model.next_asset_id == 'gold#test'
+    response.account_assets_response.ClearField('next_asset_id')
+    assert round_trip(response).next_asset_id is None
+
+
+def test_transactions_page_decodes_commands():
+    iroha = Iroha('admin@test')
+    tx = iroha.transaction([
+        commands.TransferAsset('admin@test', 'alice@test', 'coin#test', '2.50', 'rent'),
+        iroha.command('CreateRole', role_name='user', permissions=[1, 12]),
+    ], quorum=2)
+    IrohaCrypto.sign_transaction(tx, ADMIN_PRIVATE_KEY)
+    response = QueryResponse()
+    response.transactions_page_response.transactions.extend([tx])
+    response.transactions_page_response.all_transactions_size = 1
+    model = round_trip(response)
+    transaction = model.transactions[0]
+    assert transaction.commands[0] == commands.TransferAsset(
+        'admin@test', 'alice@test', 'coin#test', '2.50', 'rent')
+    assert transaction.quorum == 2
+    assert transaction.hash == IrohaCrypto.hash(tx).hex()
+    assert model.next_tx_hash is None
+
+
+def test_peers_and_engine_receipts_round_trip():
+    response = QueryResponse()
+    response.peers_response.peers.add(address='127.0.0.1:10001', peer_key='ab' * 32, syncing_peer=True)
+    assert round_trip(response).peers[0].tls_certificate is None
+    response = QueryResponse()
+    receipt = response.engine_receipts_response.engine_receipts.add(command_index=1, caller='admin@test')
+    receipt.contract_address = 'cd' * 20
+    receipt.logs.add(address='cd' * 20, data='00', topics=['ee'])
+    model = round_trip(response)
+    assert model.engine_receipts[0].call_result is None
+    assert model.engine_receipts[0].logs[0].topics == ['ee']
+
+
+def test_error_response_raises():
+    response = QueryResponse()
+    response.error_response.reason = response.error_response.NO_ACCOUNT
+    with pytest.raises(QueryError):
+        models.from_query_response(response)
@@ -0,0 +1,63 @@
+"""Tests of the multi-signature co-signing workflow"""
+
+import pytest
+
+from iroha import Iroha, IrohaCrypto
+from iroha.mst import ENOUGH_SIGNATURES_COLLECTED, MST_PENDING, MstWorkflow, PendingBatch, PendingTransaction, mst_state
+from iroha.testing.mock_node import ADMIN_PRIVATE_KEY, MockIrohaNode
+
+SECOND_PRIVATE_KEY = 'cc5013e43918bd0e5c4d800416c88bed77892ff077929162bb03ead40a745e88'
+
+
+@pytest.fixture
+def node():
+    with MockIrohaNode() as mock_node:
+        iroha = Iroha('admin@test')
+        tx = iroha.transaction([
+            iroha.command('AddSignatory', account_id='admin@test',
+                          public_key=IrohaCrypto.derive_public_key(SECOND_PRIVATE_KEY)),
+            iroha.command('SetAccountQuorum', account_id='admin@test', quorum=2),
+        ])
+        mock_node.client().send_tx_await(IrohaCrypto.sign_transaction(tx, ADMIN_PRIVATE_KEY), timeout=5)
+        yield mock_node
+
+
+def test_pending_transaction_is_co_signed(node):
+    iroha = Iroha('admin@test')
+    net = node.client()
+    tx = iroha.transaction([iroha.command('AddAssetQuantity', asset_id='coin#test', amount='1')], quorum=2)
+    net.send_tx(IrohaCrypto.sign_transaction(tx, ADMIN_PRIVATE_KEY))
+
+    workflow = MstWorkflow(iroha, net, SECOND_PRIVATE_KEY)
+    pending, = workflow.awaiting()
+    assert pending.missing_count == 1
+    assert pending.missing_keys == [workflow.public_key]
+    assert workflow.states(pending) == {pending.tx_hash: MST_PENDING}
+    assert MstWorkflow(iroha, net, ADMIN_PRIVATE_KEY).awaiting() == []
+
+    assert workflow.co_sign(pending) == [pending.tx_hash]
+    assert workflow.wait(pending, timeout=5) == {pending.tx_hash: ENOUGH_SIGNATURES_COLLECTED}
+    assert workflow.pending() == []
+    assert net.tx_status(tx)[0] == 'COMMITTED'
+
+
+def test_mst_states():
+    assert mst_state('MST_EXPIRED') == 'MST_EXPIRED'
+    assert mst_state('COMMITTED') == ENOUGH_SIGNATURES_COLLECTED
+    assert mst_state('NOT_RECEIVED') is None
+    assert mst_state('STATELESS_VALIDATION_SUCCESS') == MST_PENDING
+    assert mst_state('ENOUGH_SIGNATURES_COLLECTED') == ENOUGH_SIGNATURES_COLLECTED
+
+
+def test_incomplete_batch_is_not_co_signed(node):
+    iroha = Iroha('admin@test')
+    net = node.client()
+    txs = [iroha.transaction([iroha.command('AddAssetQuantity', asset_id='coin#test', amount=amount)], quorum=2)
+           for amount in ('1', '2')]
+    iroha.batch(txs, atomic=True)
+    batch = PendingBatch([PendingTransaction(txs[0])])
+    assert not batch.is_complete
+
+    with pytest.raises(ValueError):
+        MstWorkflow(iroha, net, SECOND_PRIVATE_KEY).co_sign(batch)
+    assert net.tx_status(txs[0])[0] == 'NOT_RECEIVED'
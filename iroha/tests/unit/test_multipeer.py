@@ -0,0 +1,181 @@
+"""Tests of the multi-peer client failover"""
+
+import grpc
+import pytest
+
+from iroha import BlockStreamError, Iroha, IrohaCrypto, TransactionTimeoutError, qry_responses_pb2
+from iroha.multipeer import HighestHeightPolicy, IrohaMultiPeerGrpc
+from iroha.testing.faults import Delay, Fail, FaultInjectingGrpc, Respond, error_response, tx_statuses
+from iroha.testing.mock_node import ADMIN_PRIVATE_KEY, MockIrohaNode
+
+ADDRESSES = ['127.0.0.1:50051', '127.0.0.1:50052']
+
+
+@pytest.fixture
+def node():
+    with MockIrohaNode() as mock_node:
+        yield mock_node
+
+
+@pytest.fixture
+def iroha():
+    return Iroha('admin@test')
+
+
+def faulty_peers(net, node):
+    for peer in net.peers:
+        peer.client = FaultInjectingGrpc(node.address)
+    return [peer.client for peer in net.peers]
+
+
+def signed_tx(iroha, amount='1'):
+    return IrohaCrypto.sign_transaction(
+        iroha.transaction([Iroha.command('AddAssetQuantity', asset_id='coin#test', amount=amount)]),
+        ADMIN_PRIVATE_KEY)
+
+
+def commit_blocks(node, iroha, count):
+    for amount in range(1, count + 1):
+        node.client().send_tx_await(signed_tx(iroha, str(amount)), timeout=5)
+
+
+def block_responses(node, *heights):
+    responses = []
+    for height in heights:
+        response = qry_responses_pb2.BlockQueryResponse()
+        response.block_response.block.CopyFrom(node.blocks[height - 1])
+        responses.append(response)
+    return responses
+
+
+def test_transaction_is_awaited_on_the_accepting_peer(node, iroha):
+    net = IrohaMultiPeerGrpc(ADDRESSES)
+    first, second = faulty_peers(net, node)
+    first.inject('Torii', Fail(grpc.StatusCode.UNAVAILABLE))
+    result = net.send_tx_await(signed_tx(iroha), timeout=5)
+    assert result.is_committed
+    assert first.call_count('StatusStream') == 0
+    assert second.call_count('StatusStream') == 1
+    assert not net.peers[0].is_available
+
+
+def test_transaction_is_awaited_within_the_remaining_time(node, iroha):
+    net = IrohaMultiPeerGrpc(ADDRESSES)
+    first, second = faulty_peers(net, node)
+    tx = signed_tx(iroha)
+    first.inject('Torii', Delay(0.8, then=Fail(grpc.StatusCode.UNAVAILABLE)))
+    second.inject('StatusStream', tx_statuses(IrohaCrypto.hash(tx), 'ENOUGH_SIGNATURES_COLLECTED'))
+    with pytest.raises(TransactionTimeoutError):
+        net.send_tx_await(tx, timeout=1.5)
+    assert second.call_count('StatusStream') == 1
+
+
+def test_failed_peer_is_tried_last_during_cooldown(node):
+    net = IrohaMultiPeerGrpc(ADDRESSES, cooldown=60)
+    first, second = faulty_peers(net, node)
+    first.inject('Healthcheck', Fail(grpc.StatusCode.DEADLINE_EXCEEDED))
+    for _ in range(3):
+        assert net.healthcheck(timeout=5).last_block_height == 1
+    assert first.call_count('Healthcheck') == 1
+    assert second.call_count('Healthcheck') == 3
+
+    net = IrohaMultiPeerGrpc(ADDRESSES, cooldown=0)
+    first, second = faulty_peers(net, node)
+    first.inject('Healthcheck', Fail(grpc.StatusCode.UNAVAILABLE))
+    for _ in range(3):
+        net.healthcheck(timeout=5)
+    assert first.call_count('Healthcheck') == 2
+
+
+def test_other_errors_are_not_failed_over(node):
+    net = IrohaMultiPeerGrpc(ADDRESSES)
+    first, second = faulty_peers(net, node)
+    first.inject('Healthcheck', Fail(grpc.StatusCode.PERMISSION_DENIED))
+    with pytest.raises(grpc.RpcError) as error:
+        net.healthcheck(timeout=5)
+    assert error.value.code() == grpc.StatusCode.PERMISSION_DENIED
+    assert second.call_count('Healthcheck') == 0
+    assert net.peers[0].is_available
+
+
+def test_peers_are_ordered_by_height(node):
+    net = IrohaMultiPeerGrpc(ADDRESSES)
+    first, second = faulty_peers(net, node)
+    first.inject('Healthcheck', Respond(qry_responses_pb2.HealthcheckData(last_block_height=3)))
+    second.inject('Healthcheck', Respond(qry_responses_pb2.HealthcheckData(last_block_height=5)))
+    policy = HighestHeightPolicy(refresh_interval=60)
+    assert [peer.client for peer in policy.order(net.peers)] == [second, first]
+    assert [peer.last_block_height for peer in net.peers] == [3, 5]
+    policy.order(net.peers)
+    assert first.call_count('Healthcheck') == 1
+
+
+def test_blocks_stream_is_resumed_on_another_peer(node, iroha):
+    commit_blocks(node, iroha, 3)
+    assert node.height == 4
+
+    net = IrohaMultiPeerGrpc(ADDRESSES, private_key=ADMIN_PRIVATE_KEY, query_counter=iroha.query_counter)
+    first, second = faulty_peers(net, node)
+    first.inject('FetchCommits', Respond(block_responses(node, 2), code=grpc.StatusCode.UNAVAILABLE))
+    second.inject('FetchCommits', Respond(block_responses(node, 2, 4)))
+    query = IrohaCrypto.sign_query(iroha.blocks_query(), ADMIN_PRIVATE_KEY)
+    heights = [response.block_response.block.block_v1.payload.height
+               for response in net.send_blocks_stream_query(query, timeout=5)]
+    assert heights == [2, 3, 4]
+
+    requests = {method: request for method, request in second.calls}
+    assert requests['FetchCommits'].meta.query_counter == 2
+    assert requests['Find'].payload.meta.query_counter == 3
+    assert requests['Find'].payload.get_block.height == 3
+    assert iroha.query_counter.last == 3
+
+
+def test_blocks_stream_fails_when_all_peers_fail(node, iroha):
+    net = IrohaMultiPeerGrpc(ADDRESSES)
+    for client in faulty_peers(net, node):
+        client.inject('FetchCommits', Fail(grpc.StatusCode.UNAVAILABLE))
+    query = IrohaCrypto.sign_query(iroha.blocks_query(), ADMIN_PRIVATE_KEY)
+    with pytest.raises(grpc.RpcError) as error:
+        list(net.send_blocks_stream_query(query, timeout=5))
+    assert error.value.code() == grpc.StatusCode.UNAVAILABLE
+
+
+def test_blocks_stream_survives_failures_separated_by_blocks(node, iroha):
+    commit_blocks(node, iroha, 3)
+    net = IrohaMultiPeerGrpc(ADDRESSES)
+    first, second = faulty_peers(net, node)
+    first.inject('FetchCommits',
+                 Respond(block_responses(node, 2), code=grpc.StatusCode.UNAVAILABLE),
+                 Respond(block_responses(node, 4)))
+    second.inject('FetchCommits', Respond(block_responses(node, 3), code=grpc.StatusCode.UNAVAILABLE))
+    query = IrohaCrypto.sign_query(iroha.blocks_query(), ADMIN_PRIVATE_KEY)
+    heights = [response.block_response.block.block_v1.payload.height
+               for response in net.send_blocks_stream_query(query, timeout=5)]
+    assert heights == [2, 3, 4]
+
+
+def test_missed_blocks_without_private_key_fail_the_stream(node, iroha):
+    commit_blocks(node, iroha, 3)
+    net = IrohaMultiPeerGrpc(ADDRESSES)
+    first, second = faulty_peers(net, node)
+    first.inject('FetchCommits', Respond(block_responses(node, 2), code=grpc.StatusCode.UNAVAILABLE))
+    second.inject('FetchCommits', Respond(block_responses(node, 4)))
+    query = IrohaCrypto.sign_query(iroha.blocks_query(), ADMIN_PRIVATE_KEY)
+    stream = net.send_blocks_stream_query(query, timeout=5)
+    assert next(stream).block_response.block.block_v1.payload.height == 2
+    with pytest.raises(BlockStreamError):
+        next(stream)
+
+
+def test_unavailable_missed_block_fails_the_stream(node, iroha):
+    commit_blocks(node, iroha, 3)
+    net = IrohaMultiPeerGrpc(ADDRESSES, private_key=ADMIN_PRIVATE_KEY)
+    first, second = faulty_peers(net, node)
+    first.inject('FetchCommits', Respond(block_responses(node, 2), code=grpc.StatusCode.UNAVAILABLE))
+    second.inject('FetchCommits', Respond(block_responses(node, 4)))
+    second.inject('Find', error_response('STATEFUL_INVALID', 'Invalid height 3', 3))
+    query = IrohaCrypto.sign_query(iroha.blocks_query(), ADMIN_PRIVATE_KEY)
+    stream = net.send_blocks_stream_query(query, timeout=5)
+    assert next(stream).block_response.block.block_v1.payload.height == 2
+    with pytest.raises(BlockStreamError):
+        next(stream)
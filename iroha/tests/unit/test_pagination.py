@@ -0,0 +1,96 @@
+"""Tests of auto-paginating generators of paged queries"""
+
+import pytest
+
+from iroha import Iroha, IrohaCrypto, UnexpectedQueryResponseError, qry_responses_pb2
+from iroha.pagination import IrohaPaginator
+from iroha.testing.faults import FaultInjectingGrpc, Respond
+from iroha.testing.mock_node import ADMIN_PRIVATE_KEY, MockIrohaNode
+
+
+@pytest.fixture
+def node():
+    with MockIrohaNode() as mock_node:
+        yield mock_node
+
+
+@pytest.fixture
+def iroha():
+    return Iroha('admin@test')
+
+
+def signed(iroha, commands, quorum=1):
+    return IrohaCrypto.sign_transaction(iroha.transaction(commands, quorum=quorum), ADMIN_PRIVATE_KEY)
+
+
+def detail(key):
+    return Iroha.command('SetAccountDetail', account_id='admin@test', key=key, value='v')
+
+
+def test_transactions_follow_next_tx_hash_until_last_page(node, iroha):
+    txs = [signed(iroha, [detail('k{}'.format(index))]) for index in range(5)]
+    node.submit(txs)
+    net = FaultInjectingGrpc(node.address)
+    pages = IrohaPaginator(iroha, net, ADMIN_PRIVATE_KEY, page_size=2)
+    assert [IrohaCrypto.hash(tx) for tx in pages.account_transactions('admin@test')] \
+        == [IrohaCrypto.hash(tx) for tx in txs]
+    assert net.call_count('Find') == 3
+
+
+def test_full_last_page_is_not_followed_by_empty_one(node, iroha):
+    node.submit([signed(iroha, [detail('k{}'.format(index))]) for index in range(4)])
+    net = FaultInjectingGrpc(node.address)
+    assert len(list(IrohaPaginator(iroha, net, ADMIN_PRIVATE_KEY, page_size=2).account_transactions('admin@test'))) == 4
+    assert net.call_count('Find') == 2
+
+
+def test_empty_first_page(node, iroha):
+    net = FaultInjectingGrpc(node.address)
+    assert list(IrohaPaginator(iroha, net, ADMIN_PRIVATE_KEY).account_transactions('test@test')) == []
+    assert net.call_count('Find') == 1
+
+
+def test_assets_follow_next_asset_id(node, iroha):
+    commands = []
+    for index in range(3):
+        commands.append(Iroha.command('CreateAsset', asset_name='a{}'.format(index), domain_id='test', precision=0))
+        commands.append(Iroha.command('AddAssetQuantity', asset_id='a{}#test'.format(index), amount='1'))
+    node.submit([signed(iroha, commands)])
+    net = FaultInjectingGrpc(node.address)
+    assets = IrohaPaginator(iroha, net, ADMIN_PRIVATE_KEY, page_size=2).account_assets('admin@test')
+    assert [(asset.asset_id, asset.balance) for asset in assets] \
+        == [('a0#test', '1'), ('a1#test', '1'), ('a2#test', '1')]
+    assert net.call_count('Find') == 2
+
+
+def test_details_follow_next_record_id(node, iroha):
+    node.submit([signed(iroha, [detail('k0'), detail('k1'), detail('k2')])])
+    net = FaultInjectingGrpc(node.address)
+    records = IrohaPaginator(iroha, net, ADMIN_PRIVATE_KEY, page_size=2).account_details('admin@test')
+    assert list(records) == [('admin@test', 'k0', 'v'), ('admin@test', 'k1', 'v'), ('admin@test', 'k2', 'v')]
+    assert net.call_count('Find') == 2
+
+
+def test_pending_transactions_follow_next_batch_info(node, iroha):
+    first = signed(iroha, [detail('first')], quorum=2)
+    batch = [iroha.transaction([detail('batch{}'.format(index))], quorum=2) for index in range(2)]
+    Iroha.batch(batch)
+    for tx in batch:
+        IrohaCrypto.sign_transaction(tx, ADMIN_PRIVATE_KEY)
+    last = signed(iroha, [detail('last')], quorum=2)
+    node.submit([first])
+    node.submit(batch)
+    node.submit([last])
+    net = FaultInjectingGrpc(node.address)
+    pending = IrohaPaginator(iroha, net, ADMIN_PRIVATE_KEY, page_size=1).pending_transactions()
+    assert [IrohaCrypto.hash(tx) for tx in pending] == [IrohaCrypto.hash(tx) for tx in [first] + batch + [last]]
+    page_sizes = [request.payload.get_pending_transactions.pagination_meta.page_size for _, request in net.calls]
+    assert page_sizes == [1, 2, 2]
+
+
+def test_unexpected_response_type_is_reported(iroha):
+    response = qry_responses_pb2.QueryResponse()
+    response.account_response.account.account_id = 'admin@test'
+    net = FaultInjectingGrpc().inject('Find', Respond(response))
+    with pytest.raises(UnexpectedQueryResponseError):
+        list(IrohaPaginator(iroha, net, ADMIN_PRIVATE_KEY).account_assets('admin@test'))
@@ -0,0 +1,46 @@
+"""Tests of protobuf JSON import and export"""
+
+import json
+import os
+
+from iroha import Iroha, IrohaCrypto, protojson
+from iroha.testing.mock_node import ADMIN_PRIVATE_KEY
+
+GENESIS_PATH = os.path.join(os.path.dirname(__file__), '..', '..', '..', 'docker', 'iroha', 'genesis.block')
+
+
+def test_transaction_hash_survives_json():
+    iroha = Iroha('admin@test')
+    tx = iroha.transaction([
+        iroha.command('CreateRole', role_name='user', permissions=[12]),
+        iroha.command('TransferAsset', src_account_id='admin@test', dest_account_id='test@test',
+                      asset_id='coin#test', amount='1.5'),
+    ])
+    iroha.batch([tx], atomic=False)
+    IrohaCrypto.sign_transaction(tx, ADMIN_PRIVATE_KEY)
+    text = protojson.to_json(tx)
+    data = json.loads(text)
+    reduced_payload = data['payload']['reducedPayload']
+    assert reduced_payload['commands'][0]['createRole']['permissions'] == ['can_transfer']
+    assert reduced_payload['createdTime'] == str(tx.payload.reduced_payload.created_time)
+    assert data['payload']['batch']['type'] == 'ORDERED'
+    parsed = protojson.from_json(text, 'Transaction')
+    assert parsed == tx
+    assert IrohaCrypto.hash(parsed) == IrohaCrypto.hash(tx)
+
+
+def test_genesis_block_round_trips(tmp_path):
+    block = protojson.load(GENESIS_PATH)
+    commands = block.block_v1.payload.transactions[0].payload.reduced_payload.commands
+    assert commands[0].add_peer.peer.address == '127.0.0.1:10001'
+    path = str(tmp_path / 'genesis.block')
+    protojson.save(path, block)
+    assert protojson.load(path) == block
+    assert '"reducedPayload"' in (tmp_path / 'genesis.block').read_text()
+
+
+def test_query_round_trips():
+    query = IrohaCrypto.sign_query(Iroha('admin@test').query('GetAccount', account_id='test@test'), ADMIN_PRIVATE_KEY)
+    data = protojson.to_dict(query)
+    assert data['payload']['getAccount'] == {'accountId': 'test@test'}
+    assert protojson.from_dict(data, 'Query') == query
@@ -0,0 +1,75 @@
+"""Test to check automatic query counters"""
+
+import threading
+
+import grpc
+
+from iroha import Iroha, QueryCounter, RetryPolicy
+from iroha.testing.faults import Fail, FaultInjectingGrpc, error_response
+from iroha.testing.mock_node import ADMIN_PRIVATE_KEY
+
+
+def test_queries_get_increasing_counters():
+    iroha = Iroha('admin@test')
+    first = iroha.query('GetAccount', account_id='admin@test')
+    second = iroha.query('GetAccount', account_id='admin@test')
+    blocks = iroha.blocks_query()
+    explicit = iroha.query('GetAccount', counter=42, account_id='admin@test')
+    assert first.payload.meta.query_counter == 1
+    assert second.payload.meta.query_counter == 2
+    assert blocks.meta.query_counter == 3
+    assert explicit.payload.meta.query_counter == 42
+
+
+def test_counter_is_thread_safe():
+    counter = QueryCounter()
+    issued = []
+    lock = threading.Lock()
+
+    def worker():
+        values = [counter.next() for _ in range(500)]
+        with lock:
+            issued.extend(values)
+
+    threads = [threading.Thread(target=worker) for _ in range(8)]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join()
+    assert sorted(issued) == list(range(1, 4001))
+    assert counter.last == 4000
+
+
+def test_counter_survives_restart(tmp_path):
+    path = str(tmp_path / 'counter')
+    counter = QueryCounter(path=path)
+    assert [counter.next() for _ in range(3)] == [1, 2, 3]
+    restored = QueryCounter(path=path)
+    assert restored.next() == 4
+
+
+def test_last_is_none_until_a_value_is_issued(tmp_path):
+    path = str(tmp_path / 'counter')
+    counter = QueryCounter(path=path)
+    assert counter.last is None
+    counter.next()
+    assert counter.last == 1
+    assert QueryCounter(path=path).last is None
+
+
+def test_refreshed_query_takes_counter_from_session():
+    iroha = Iroha('admin@test')
+    query = iroha.query('GetAccount', account_id='admin@test')
+    iroha.query('GetAccount', account_id='admin@test')
+    assert Iroha.refresh_query(query, query_counter=iroha.query_counter).payload.meta.query_counter == 3
+    assert Iroha.refresh_query(query).payload.meta.query_counter == 2
+
+
+def test_retried_query_is_resigned_with_session_counter():
+    iroha = Iroha('admin@test')
+    net = FaultInjectingGrpc(retry_policy=RetryPolicy(sleep=lambda delay: None), query_counter=iroha.query_counter)
+    net.inject('Find', Fail(grpc.StatusCode.UNAVAILABLE), error_response('NO_ACCOUNT'))
+    query = iroha.query('GetAccount', account_id='bob@test')
+    iroha.query('GetAccount', account_id='bob@test')
+    net.send_query(query, private_key=ADMIN_PRIVATE_KEY)
+    assert [request.payload.meta.query_counter for _, request in net.calls] == [1, 3]
@@ -0,0 +1,31 @@
+"""Test to check unwrapping of query responses"""
+
+import pytest
+
+from iroha import IrohaGrpc, NoAccountError, QueryError, UnexpectedQueryResponseError, qry_responses_pb2
+
+
+def test_successful_response_is_unwrapped():
+    response = qry_responses_pb2.QueryResponse()
+    response.account_response.account.account_id = 'admin@test'
+    inner = IrohaGrpc.unwrap_query_response(response)
+    assert isinstance(inner, qry_responses_pb2.AccountResponse)
+    assert inner.account.account_id == 'admin@test'
+
+
+def test_error_response_is_raised_by_reason():
+    response = qry_responses_pb2.QueryResponse()
+    response.error_response.reason = qry_responses_pb2.ErrorResponse.NO_ACCOUNT
+    response.error_response.message = 'no account bob@test'
+    response.error_response.error_code = 0
+    with pytest.raises(NoAccountError) as error:
+        IrohaGrpc.unwrap_query_response(response)
+    assert isinstance(error.value, QueryError)
+    assert error.value.reason == 'NO_ACCOUNT'
+    assert error.value.message == 'no account bob@test'
+
+
+def test_empty_response_is_a_client_side_error():
+    with pytest.raises(UnexpectedQueryResponseError) as error:
+        IrohaGrpc.unwrap_query_response(qry_responses_pb2.QueryResponse())
+    assert not isinstance(error.value, QueryError)
@@ -0,0 +1,79 @@
+"""Tests of the record and replay transport"""
+
+import grpc
+import pytest
+
+from iroha import Iroha, IrohaCrypto, endpoint_pb2, qry_responses_pb2
+from iroha.testing.recording import Interaction, RecordingGrpc, ReplayGrpc, ReplayMismatchError, \
+    load_interactions, save_interactions
+from iroha.testing.mock_node import ADMIN_PRIVATE_KEY, MockIrohaNode
+
+
+def account_query(counter):
+    query = Iroha('admin@test').query('GetAccount', counter=counter, account_id='admin@test')
+    return IrohaCrypto.sign_query(query, ADMIN_PRIVATE_KEY)
+
+
+@pytest.fixture
+def recording(tmp_path):
+    response = qry_responses_pb2.QueryResponse()
+    response.account_response.account.account_id = 'admin@test'
+    status_request = endpoint_pb2.TxStatusRequest(tx_hash='ab' * 32)
+    statuses = [endpoint_pb2.ToriiResponse(tx_status=endpoint_pb2.ENOUGH_SIGNATURES_COLLECTED, tx_hash='ab' * 32)]
+    path = str(tmp_path / 'calls.json')
+    save_interactions(path, [
+        Interaction('Find', account_query(1), [response]),
+        Interaction('StatusStream', status_request, statuses, ('UNAVAILABLE', 'connection reset')),
+    ])
+    return path
+
+
+def test_recording_is_replayed(recording):
+    interactions = load_interactions(recording)
+    assert [interaction.method for interaction in interactions] == ['Find', 'StatusStream']
+
+    net = ReplayGrpc(recording, strict=False)
+    response = net.send_query(account_query(2), unwrap=True)
+    assert response.account.account_id == 'admin@test'
+    stream = net.tx_hash_status_stream('ab' * 32)
+    assert next(stream)[0] == 'ENOUGH_SIGNATURES_COLLECTED'
+    with pytest.raises(grpc.RpcError) as error:
+        next(stream)
+    assert error.value.code() == grpc.StatusCode.UNAVAILABLE
+    net.assert_exhausted()
+
+
+def test_mismatch_fails_loudly(recording):
+    net = ReplayGrpc(recording)
+    with pytest.raises(ReplayMismatchError):
+        net.send_query(account_query(2))
+    with pytest.raises(ReplayMismatchError):
+        next(net.tx_hash_status_stream('ab' * 32))
+    with pytest.raises(ReplayMismatchError):
+        net.assert_exhausted()
+
+
+def scenario(net):
+    iroha = Iroha('admin@test')
+    tx = IrohaCrypto.sign_transaction(
+        iroha.transaction([Iroha.command('AddAssetQuantity', asset_id='coin#test', amount='2')]),
+        ADMIN_PRIVATE_KEY)
+    result = net.send_tx_await(tx, timeout=5)
+    query = IrohaCrypto.sign_query(iroha.query('GetAccountAssets', account_id='admin@test'), ADMIN_PRIVATE_KEY)
+    assets = net.send_query(query, unwrap=True).account_assets
+    return result.history, [(asset.asset_id, asset.balance) for asset in assets]
+
+
+def test_mock_node_calls_are_recorded_and_replayed(tmp_path):
+    path = str(tmp_path / 'calls.json')
+    with MockIrohaNode() as node:
+        net = RecordingGrpc(node.address)
+        recorded = scenario(net)
+        net.save(path)
+    assert recorded[0][-1] == 'COMMITTED'
+    assert recorded[1] == [('coin#test', '2')]
+    assert [interaction.method for interaction in load_interactions(path)] == ['Torii', 'StatusStream', 'Find']
+
+    replay = ReplayGrpc(path, strict=False)
+    assert scenario(replay) == recorded
+    replay.assert_exhausted()
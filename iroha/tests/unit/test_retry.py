@@ -0,0 +1,63 @@
+"""Test to check retry policy behaviour"""
+
+import grpc
+import pytest
+
+from iroha import RetryPolicy
+
+
+class FakeRpcError(grpc.RpcError):
+    def __init__(self, code):
+        self._code = code
+
+    def code(self):
+        return self._code
+
+
+def failing(codes, result='done'):
+    calls = []
+
+    def attempt(number):
+        calls.append(number)
+        if number <= len(codes):
+            raise FakeRpcError(codes[number - 1])
+        return result
+
+    return attempt, calls
+
+
+def test_transient_errors_are_retried():
+    delays = []
+    policy = RetryPolicy(max_attempts=3, initial_backoff=1, jitter=0, sleep=delays.append)
+    attempt, calls = failing([grpc.StatusCode.UNAVAILABLE, grpc.StatusCode.DEADLINE_EXCEEDED])
+    assert policy.run(attempt) == 'done'
+    assert calls == [1, 2, 3]
+    assert delays == [1, 2]
+
+
+def test_permanent_error_is_raised_immediately():
+    policy = RetryPolicy(sleep=lambda delay: None)
+    attempt, calls = failing([grpc.StatusCode.INVALID_ARGUMENT])
+    with pytest.raises(FakeRpcError):
+        policy.run(attempt)
+    assert calls == [1]
+
+
+def test_attempts_and_budget_are_limited():
+    policy = RetryPolicy(max_attempts=2, sleep=lambda delay: None)
+    attempt, calls = failing([grpc.StatusCode.UNAVAILABLE] * 5)
+    with pytest.raises(FakeRpcError):
+        policy.run(attempt)
+    assert calls == [1, 2]
+
+    policy = RetryPolicy(max_attempts=10, initial_backoff=1, budget=0.5, sleep=lambda delay: None)
+    attempt, calls = failing([grpc.StatusCode.UNAVAILABLE] * 5)
+    with pytest.raises(FakeRpcError):
+        policy.run(attempt)
+    assert calls == [1]
+
+
+def test_backoff_is_capped_and_jittered():
+    policy = RetryPolicy(initial_backoff=1, max_backoff=3, multiplier=2, jitter=0.5)
+    for attempt in range(1, 10):
+        assert 0 <= policy.backoff(attempt) <= 4.5
@@ -0,0 +1,41 @@
+"""Tests of the test network generator"""
+
+import json
+import os
+
+from iroha import IrohaCrypto, protojson
+from iroha import testnet
+from iroha.config import IrohaConfig
+
+
+def test_network_is_deterministic():
+    first, second = testnet.Testnet(peers=3, seed='s').files(), testnet.Testnet(peers=3, seed='s').files()
+    assert first == second
+    assert testnet.Testnet(peers=3, seed='other').files() != first
+    private_key, public_key = testnet.derive_keypair('s', 'node1')
+    assert IrohaCrypto.derive_public_key(private_key).decode() == public_key
+    assert first['iroha1/keys/node1.pub'] == public_key
+
+
+def test_genesis_lists_every_peer():
+    network = testnet.Testnet(peers=3, seed='s')
+    files = network.files()
+    genesis = protojson.from_json(files['iroha0/genesis.block'], 'Block')
+    commands = genesis.block_v1.payload.transactions[0].payload.reduced_payload.commands
+    peers = [(command.add_peer.peer.address, command.add_peer.peer.peer_key)
+             for command in commands if command.HasField('add_peer')]
+    assert peers == [(peer.address, peer.public_key) for peer in network.peers]
+    assert files['iroha2/genesis.block'] == files['iroha0/genesis.block']
+    config = json.loads(files['iroha2/config.docker'])
+    assert config['pg_opt'].startswith('host=postgres2 ')
+    assert config['mst_enable'] is True
+    assert config['load_delay'] == 5000
+    assert IrohaConfig.from_dict(config).errors() == []
+    assert "'50053:50051'" in files['docker-compose.yaml']
+
+
+def test_cli_writes_directory(tmp_path, capsys):
+    assert testnet.main([str(tmp_path), '--peers', '2', '--seed', 's', '--no-mst']) == 0
+    assert os.access(str(tmp_path / 'iroha1' / 'entrypoint.sh'), os.X_OK)
+    assert json.loads((tmp_path / 'iroha1' / 'config.docker').read_text())['mst_enable'] is False
+    assert 'iroha1 torii 127.0.0.1:50052' in capsys.readouterr().out
@@ -0,0 +1,32 @@
+"""Test to check transaction outcome collection"""
+
+import pytest
+
+from iroha import TxResult, StatefulValidationFailedError, endpoint_pb2
+
+
+def torii_response(status, **kwargs):
+    return endpoint_pb2.ToriiResponse(tx_status=endpoint_pb2.TxStatus.Value(status), **kwargs)
+
+
+def test_consecutive_statuses_are_collapsed():
+    result = TxResult('abcd')
+    for status in ('NOT_RECEIVED', 'ENOUGH_SIGNATURES_COLLECTED', 'ENOUGH_SIGNATURES_COLLECTED',
+                   'STATELESS_VALIDATION_SUCCESS', 'STATEFUL_VALIDATION_SUCCESS', 'COMMITTED'):
+        result.append(torii_response(status))
+    assert result.history == ['NOT_RECEIVED', 'ENOUGH_SIGNATURES_COLLECTED', 'STATELESS_VALIDATION_SUCCESS',
+                              'STATEFUL_VALIDATION_SUCCESS', 'COMMITTED']
+    assert result.is_final and result.is_committed
+    result.raise_for_status()
+
+
+def test_stateful_failure_details():
+    result = TxResult('abcd')
+    result.append(torii_response('STATEFUL_VALIDATION_FAILED', err_or_cmd_name='TransferAsset',
+                                 failed_cmd_index=2, error_code=6))
+    assert result.is_final and not result.is_committed
+    with pytest.raises(StatefulValidationFailedError) as error:
+        result.raise_for_status()
+    assert error.value.failed_cmd_index == 2
+    assert error.value.err_or_cmd_name == 'TransferAsset'
+    assert error.value.error_code == 6
@@ -0,0 +1,51 @@
+"""Tests of client-side stateless validation"""
+
+import pytest
+
+from iroha import Iroha, IrohaCrypto, StatelessValidationError
+from iroha.validation import Finding, StatelessValidator
+from iroha.testing.mock_node import ADMIN_PRIVATE_KEY
+
+NOW = 1600000000000
+
+
+def validator(**kwargs):
+    return StatelessValidator(clock=lambda: NOW, **kwargs)
+
+
+def test_valid_transaction_has_no_findings():
+    tx = Iroha('admin@test').transaction([
+        Iroha.command('TransferAsset', src_account_id='admin@test', dest_account_id='test@test',
+                      asset_id='coin#test', amount='1.25')], created_time=NOW)
+    IrohaCrypto.sign_transaction(tx, ADMIN_PRIVATE_KEY)
+    assert validator().validate_transaction(tx) == []
+
+
+def test_findings_point_to_commands():
+    tx = Iroha('admin@test').transaction([
+        Iroha.command('CreateDomain', domain_id='test', default_role='user'),
+        Iroha.command('AddAssetQuantity', asset_id='coin#test', amount='1.255'),
+        Iroha.command('AddSignatory', account_id='Admin@test', public_key='abcd'),
+    ], quorum=0, created_time=NOW - 25 * 60 * 60 * 1000)
+    findings = validator(asset_precisions={'coin#test': 2}).validate_transaction(tx)
+    assert [(finding.command_index, finding.field) for finding in findings] == [
+        (None, 'reduced_payload.quorum'),
+        (None, 'reduced_payload.created_time'),
+        (1, 'add_asset_quantity.amount'),
+        (2, 'add_signatory.account_id'),
+        (2, 'add_signatory.public_key'),
+        (None, 'signatures'),
+    ]
+
+
+def test_empty_transaction_and_query():
+    tx = Iroha('admin@test').transaction([], created_time=NOW)
+    assert Finding(None, 'reduced_payload.commands', 'transaction has no commands') in \
+        validator().validate_transaction(tx, require_signatures=False)
+
+    query = Iroha('admin@test').query('GetAccountTransactions', account_id='admin@test',
+                                      created_time=NOW + 10 * 60 * 1000)
+    with pytest.raises(StatelessValidationError) as error:
+        validator().check_query(query)
+    assert [finding.field for finding in error.value.findings] == [
+        'payload.meta.created_time', 'get_account_transactions.pagination_meta', 'signature']
@@ -0,0 +1,475 @@
+#!/usr/bin/env python3
+#
+# Copyright Soramitsu Co., Ltd. All Rights Reserved.
+# SPDX-License-Identifier: Apache-2.0
+#
+
+import re
+import time
+
+from . import commands_pb2
+from . import primitive_pb2
+from .amount import AMOUNT_PATTERN, MAX_PRECISION, Amount
+from .errors import StatelessValidationError, ValidationError
+
+ACCOUNT_NAME_PATTERN = re.compile(r'^[a-z_0-9]{1,32}$')
+ASSET_NAME_PATTERN = ACCOUNT_NAME_PATTERN
+ROLE_NAME_PATTERN = ACCOUNT_NAME_PATTERN
+DOMAIN_LABEL = r'[a-zA-Z]([a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?'
+DOMAIN_PATTERN = re.compile(r'^({label}\.)*{label}$'.format(label=DOMAIN_LABEL))
+PUBLIC_KEY_PATTERN = re.compile(r'^(ed0120)?[0-9a-fA-F]{64}$')
+SIGNATURE_PATTERN = re.compile(r'^[0-9a-fA-F]{128}$')
+HASH_PATTERN = re.compile(r'^[0-9a-fA-F]{64}$')
+DETAIL_KEY_PATTERN = re.compile(r'^[a-zA-Z0-9_]{1,64}$')
+HEX_PATTERN = re.compile(r'^([0-9a-fA-F]{2})*$')
+PEER_ADDRESS_PATTERN = re.compile(r'^[^\s:]+:[0-9]{1,5}$')
+MAX_DETAIL_VALUE_LENGTH = 4096
+MAX_DESCRIPTION_LENGTH = 64
+MAX_QUORUM = 128
+# allowed distance of created_time from the peer clock in milliseconds
+MAX_PAST_DELAY = 24 * 60 * 60 * 1000
+MAX_FUTURE_DELAY = 5 * 60 * 1000
+
+
+class Finding(object):
+    """
+    Single violation of a stateless validation rule
+    """
+
+    def __init__(self, command_index, field, message):
+        """
+        :param command_index: index of the command in the transaction, None for other fields
+        :param field: dotted path of the field, e.g. 'transfer_asset.amount' or 'reduced_payload.quorum'
+        :param message: description of the violation
+        """
+        self.command_index = command_index
+        self.field = field
+        self.message = message
+
+    def __eq__(self, other):
+        return isinstance(other, Finding) and \
+            (self.command_index, self.field, self.message) == (other.command_index, other.field, other.message)
+
+    def __repr__(self):
+        return 'Finding(command_index={!r}, field={!r}, message={!r})'.format(
+            self.command_index, self.field, self.message)
+
+    def __str__(self):
+        if self.command_index is None:
+            return '{}: {}'.format(self.field, self.message)
+        return 'command #{} {}: {}'.format(self.command_index, self.field, self.message)
+
+
+# *** Rules: each of them returns a description of the problem or None *** #
+
+def _type_error(value, expected_type, name):
+    if isinstance(value, bool) and expected_type is not bool:
+        return '{} expected, got bool'.format(name)
+    if not isinstance(value, expected_type):
+        return '{} expected, got {}'.format(name, type(value).__name__)
+    return None
+
+
+def string_error(value):
+    return _type_error(value, str, 'string')
+
+
+def _pattern_error(value, pattern, what):
+    error = string_error(value)
+    if error:
+        return error
+    if not pattern.match(value):
+        return '{!r} is not a valid {}'.format(value, what)
+    return None
+
+
+def account_name_error(value):
+    return _pattern_error(value, ACCOUNT_NAME_PATTERN, 'account name')
+
+
+def asset_name_error(value):
+    return _pattern_error(value, ASSET_NAME_PATTERN, 'asset name')
+
+
+def role_name_error(value):
+    return _pattern_error(value, ROLE_NAME_PATTERN, 'role name')
+
+
+def domain_id_error(value):
+    return _pattern_error(value, DOMAIN_PATTERN, 'domain id')
+
+
+def _compound_id_error(value, separator, name_pattern, what):
+    error = string_error(value)
+    if error:
+        return error
+    name, found, domain = value.partition(separator)
+    if not found or not name_pattern.match(name) or not DOMAIN_PATTERN.match(domain):
+        return '{!r} is not a valid {}'.format(value, what)
+    return None
+
+
+def account_id_error(value):
+    return _compound_id_error(value, '@', ACCOUNT_NAME_PATTERN, 'account id')
+
+
+def asset_id_error(value):
+    return _compound_id_error(value, '#', ASSET_NAME_PATTERN, 'asset id')
+
+
+def public_key_error(value):
+    error = string_error(value)
+    if error:
+        return error
+    if not PUBLIC_KEY_PATTERN.match(value):
+        return 'public key has to be 32 bytes hex encoded, optionally prefixed with ed0120, got {} characters'.format(
+            len(value))
+    return None
+
+
+def signature_error(value):
+    return _pattern_error(value, SIGNATURE_PATTERN, 'hex encoded 64 bytes signature')
+
+
+def hash_error(value):
+    return _pattern_error(value, HASH_PATTERN, 'hex encoded 32 bytes hash')
+
+
+def hex_error(value):
+    return _pattern_error(value, HEX_PATTERN, 'hex string')
+
+
+def peer_address_error(value):
+    return _pattern_error(value, PEER_ADDRESS_PATTERN, 'peer address')
+
+
+def detail_key_error(value):
+    return _pattern_error(value, DETAIL_KEY_PATTERN, 'detail key')
+
+
+def _length_error(value, limit):
+    error = string_error(value)
+    if error:
+        return error
+    if len(value) > limit:
+        return 'longer than {} characters'.format(limit)
+    return None
+
+
+def detail_value_error(value):
+    return _length_error(value, MAX_DETAIL_VALUE_LENGTH)
+
+
+def description_error(value):
+    return _length_error(value, MAX_DESCRIPTION_LENGTH)
+
+
+def amount_error(value, precision=None):
+    """
+    :param value: amount string
+    :param precision: optional precision of the asset, MAX_PRECISION is used if unknown
+    """
+    error = _pattern_error(value, AMOUNT_PATTERN, 'amount')
+    if error:
+        return error
+    try:
+        amount = Amount(value, precision)
+    except ValidationError as validation_error:
+        return validation_error.message
+    if not amount:
+        return 'amount has to be positive'
+    return None
+
+
+def _range_error(value, minimum, maximum):
+    error = _type_error(value, int, 'integer')
+    if error:
+        return error
+    if not minimum <= value <= maximum:
+        return '{} is out of range [{}, {}]'.format(value, minimum, maximum)
+    return None
+
+
+def quorum_error(value):
+    return _range_error(value, 1, MAX_QUORUM)
+
+
+def precision_error(value):
+    return _range_error(value, 0, MAX_PRECISION)
+
+
+def bool_error(value):
+    return _type_error(value, bool, 'bool')
+
+
+def enum_error(value, enum_type):
+    if isinstance(value, bool) or not isinstance(value, int) or value not in enum_type.values():
+        return 'unknown {} {!r}'.format(enum_type.DESCRIPTOR.name, value)
+    return None
+
+
+def role_permission_error(value):
+    return enum_error(value, primitive_pb2.RolePermission)
+
+
+def grantable_permission_error(value):
+    return enum_error(value, primitive_pb2.GrantablePermission)
+
+
+def engine_type_error(value):
+    return enum_error(value, commands_pb2.CallEngine.EngineType)
+
+
+def created_time_error(value, now=None, max_past_delay=MAX_PAST_DELAY, max_future_delay=MAX_FUTURE_DELAY):
+    """
+    :param value: creation time in milliseconds
+    :param now: current time in milliseconds, the local clock is default
+    """
+    if now is None:
+        now = int(round(time.time() * 1000))
+    if value < now - max_past_delay:
+        return 'created {} ms ago, older than {} ms'.format(now - value, max_past_delay)
+    if value > now + max_future_delay:
+        return 'created {} ms in the future, more than {} ms'.format(value - now, max_future_delay)
+    return None
+
+
+# rules applied to fields of each command, fields without rules are not checked
+COMMAND_RULES = {
+    'add_asset_quantity': {'asset_id': asset_id_error, 'amount': amount_error},
+    'add_peer': {'peer.address': peer_address_error, 'peer.peer_key': public_key_error},
+    'add_signatory': {'account_id': account_id_error, 'public_key': public_key_error},
+    'append_role': {'account_id': account_id_error, 'role_name': role_name_error},
+    'create_account': {'account_name': account_name_error, 'domain_id': domain_id_error,
+                       'public_key': public_key_error},
+    'create_asset': {'asset_name': asset_name_error, 'domain_id': domain_id_error, 'precision': precision_error},
+    'create_domain': {'domain_id': domain_id_error, 'default_role': role_name_error},
+    'create_role': {'role_name': role_name_error},
+    'detach_role': {'account_id': account_id_error, 'role_name': role_name_error},
+    'grant_permission': {'account_id': account_id_error, 'permission': grantable_permission_error},
+    'remove_signatory': {'account_id': account_id_error, 'public_key': public_key_error},
+    'revoke_permission': {'account_id': account_id_error, 'permission': grantable_permission_error},
+    'set_account_detail': {'account_id': account_id_error, 'key': detail_key_error, 'value': detail_value_error},
+    'set_account_quorum': {'account_id': account_id_error, 'quorum': quorum_error},
+    'subtract_asset_quantity': {'asset_id': asset_id_error, 'amount': amount_error},
+    'transfer_asset': {'src_account_id': account_id_error, 'dest_account_id': account_id_error,
+                       'asset_id': asset_id_error, 'description': description_error, 'amount': amount_error},
+    'remove_peer': {'public_key': public_key_error},
+    'compare_and_set_account_detail': {'account_id': account_id_error, 'key': detail_key_error,
+                                       'value': detail_value_error},
+    'set_setting_value': {},
+    'call_engine': {'type': engine_type_error, 'caller': account_id_error, 'callee': hex_error,
+                    'input': hex_error},
+}
+
+# rules applied to fields of each query
+QUERY_RULES = {
+    'get_account': {'account_id': account_id_error},
+    'get_signatories': {'account_id': account_id_error},
+    'get_account_transactions': {'account_id': account_id_error},
+    'get_account_asset_transactions': {'account_id': account_id_error, 'asset_id': asset_id_error},
+    'get_transactions': {},
+    'get_account_assets': {'account_id': account_id_error},
+    'get_account_detail': {'account_id': account_id_error, 'writer': account_id_error, 'key': detail_key_error},
+    'get_roles': {},
+    'get_role_permissions': {'role_id': role_name_error},
+    'get_asset_info': {'asset_id': asset_id_error},
+    'get_pending_transactions': {},
+    'get_block': {},
+    'get_peers': {},
+    'get_engine_receipts': {'tx_hash': hash_error},
+}
+
+
+def _field_value(message, path):
+    """
+    :return: a tuple of presence flag and value of a dotted field path
+    """
+    *parents, name = path.split('.')
+    for parent in parents:
+        message = getattr(message, parent)
+    field = message.DESCRIPTOR.fields_by_name[name]
+    if field.containing_oneof is not None and not message.HasField(name):
+        return False, None
+    return True, getattr(message, name)
+
+
+class StatelessValidator(object):
+    """
+    Local counterpart of Iroha 1.x stateless validation of transactions and queries
+    """
+
+    def __init__(self, max_past_delay=MAX_PAST_DELAY, max_future_delay=MAX_FUTURE_DELAY,
+                 asset_precisions=None, clock=None):
+        """
+        :param max_past_delay: how old created_time may be in milliseconds
+        :param max_future_delay: how far in the future created_time may be in milliseconds
+        :param asset_precisions: optional dict of asset ids to their precisions
+        to check amounts of known assets exactly
+        :param clock: function returning current time in milliseconds, the local clock is default
+        """
+        self.max_past_delay = max_past_delay
+        self.max_future_delay = max_future_delay
+        self.asset_precisions = dict(asset_precisions) if asset_precisions else {}
+        self._clock = clock if clock else lambda: int(round(time.time() * 1000))
+
+    def validate_transaction(self, transaction, require_signatures=True):
+        """
+        :param transaction: proto transaction
+        :param require_signatures: report a transaction without signatures,
+        disable to validate transactions before signing
+        :return: list of Finding, empty if the transaction is valid
+        """
+        findings = []
+        reduced_payload = transaction.payload.reduced_payload
+        self._check(findings, None, 'reduced_payload.creator_account_id',
+                    account_id_error(reduced_payload.creator_account_id))
+        self._check(findings, None, 'reduced_payload.quorum', quorum_error(reduced_payload.quorum))
+        self._check(findings, None, 'reduced_payload.created_time', self._created_time_error(
+            reduced_payload.created_time))
+        if not reduced_payload.commands:
+            findings.append(Finding(None, 'reduced_payload.commands', 'transaction has no commands'))
+        for index, command in enumerate(reduced_payload.commands):
+            findings.extend(self.validate_command(command, index))
+        if transaction.payload.HasField('batch'):
+            for reduced_hash in transaction.payload.batch.reduced_hashes:
+                self._check(findings, None, 'batch.reduced_hashes', hash_error(reduced_hash))
+        if require_signatures and not transaction.signatures:
+            findings.append(Finding(None, 'signatures', 'transaction is not signed'))
+        self._check_signatures(findings, transaction.signatures)
+        return findings
+
+    def validate_command(self, command, index=None):
+        """
+        :param command: proto command
+        :param index: index of the command in its transaction
+        :return: list of Finding
+        """
+        field_name = command.WhichOneof('command')
+        if field_name is None:
+            return [Finding(index, 'command', 'command is empty')]
+        internal_command = getattr(command, field_name)
+        findings = []
+        for path, rule in COMMAND_RULES[field_name].items():
+            present, value = _field_value(internal_command, path)
+            if not present:
+                continue
+            if rule is amount_error:
+                error = amount_error(value, self.asset_precisions.get(internal_command.asset_id))
+            else:
+                error = rule(value)
+            self._check(findings, index, '{}.{}'.format(field_name, path), error)
+        if field_name == 'transfer_asset' and internal_command.src_account_id == internal_command.dest_account_id:
+            findings.append(Finding(index, 'transfer_asset.dest_account_id',
+                                    'source and destination accounts are the same'))
+        if field_name == 'create_role':
+            for permission in internal_command.permissions:
+                self._check(findings, index, 'create_role.permissions', role_permission_error(permission))
+        if field_name == 'set_setting_value' and not internal_command.key:
+            findings.append(Finding(index, 'set_setting_value.key', 'setting key is empty'))
+        return findings
+
+    def validate_query(self, query):
+        """
+        :param query: proto Query or BlocksQuery
+        :return: list of Finding, empty if the query is valid
+        """
+        findings = []
+        meta = query.payload.meta if hasattr(query, 'payload') else query.meta
+        prefix = 'payload.meta' if hasattr(query, 'payload') else 'meta'
+        self._check(findings, None, prefix + '.creator_account_id', account_id_error(meta.creator_account_id))
+        self._check(findings, None, prefix + '.created_time', self._created_time_error(meta.created_time))
+        if hasattr(query, 'payload'):
+            findings.extend(self._validate_query_payload(query.payload))
+        if not query.HasField('signature'):
+            findings.append(Finding(None, 'signature', 'query is not signed'))
+        else:
+            self._check_signatures(findings, [query.signature])
+        return findings
+
+    def check_transaction(self, transaction, require_signatures=True):
+        """
+        :param transaction: proto transaction
+        :param require_signatures: report a transaction without signatures
+        :raise: StatelessValidationError with all the findings if the transaction is invalid
+        """
+        findings = self.validate_transaction(transaction, require_signatures)
+        if findings:
+            raise StatelessValidationError(findings)
+
+    def check_query(self, query):
+        """
+        :param query: proto Query or BlocksQuery
+        :raise: StatelessValidationError with all the findings if the query is invalid
+        """
+        findings = self.validate_query(query)
+        if findings:
+            raise StatelessValidationError(findings)
+
+    def _validate_query_payload(self, payload):
+        field_name = payload.WhichOneof('query')
+        if field_name is None:
+            return [Finding(None, 'payload.query', 'query is empty')]
+        internal_query = getattr(payload, field_name)
+        findings = []
+        for path, rule in QUERY_RULES[field_name].items():
+            present, value = _field_value(internal_query, path)
+            if present:
+                self._check(findings, None, '{}.{}'.format(field_name, path), rule(value))
+        if field_name == 'get_transactions':
+            for tx_hash in internal_query.tx_hashes:
+                self._check(findings, None, 'get_transactions.tx_hashes', hash_error(tx_hash))
+        if field_name == 'get_block' and internal_query.height == 0:
+            findings.append(Finding(None, 'get_block.height', 'height has to be positive'))
+        if 'pagination_meta' in internal_query.DESCRIPTOR.fields_by_name \
+                and internal_query.HasField('pagination_meta'):
+            pagination_meta = internal_query.pagination_meta
+            if pagination_meta.page_size == 0:
+                findings.append(Finding(None, field_name + '.pagination_meta.page_size', 'page size has to be positive'))
+            if 'first_tx_hash' in pagination_meta.DESCRIPTOR.fields_by_name \
+                    and pagination_meta.HasField('first_tx_hash'):
+                self._check(findings, None, field_name + '.pagination_meta.first_tx_hash',
+                            hash_error(pagination_meta.first_tx_hash))
+        elif field_name in ('get_account_transactions', 'get_account_asset_transactions'):
+            findings.append(Finding(None, field_name + '.pagination_meta', 'pagination is required'))
+        return findings
+
+    def _created_time_error(self, created_time):
+        return created_time_error(created_time, self._clock(), self.max_past_delay, self.max_future_delay)
+
+    @staticmethod
+    def _check_signatures(findings, signatures):
+        keys = set()
+        for signature in signatures:
+            StatelessValidator._check(findings, None, 'signatures.public_key', public_key_error(signature.public_key))
+            StatelessValidator._check(findings, None, 'signatures.signature', signature_error(signature.signature))
+            if signature.public_key.lower() in keys:
+                findings.append(Finding(None, 'signatures.public_key',
+                                        'duplicate signature of {}'.format(signature.public_key)))
+            keys.add(signature.public_key.lower())
+
+    @staticmethod
+    def _check(findings, index, field, error):
+        if error:
+            findings.append(Finding(index, field, error))
+
+
+def validate_transaction(transaction, require_signatures=True, **kwargs):
+    """
+    Validate a transaction with StatelessValidator
+    :param transaction: proto transaction
+    :param require_signatures: report a transaction without signatures
+    :param kwargs: StatelessValidator arguments
+    :return: list of Finding, empty if the transaction is valid
+    """
+    return StatelessValidator(**kwargs).validate_transaction(transaction, require_signatures)
+
+
+def validate_query(query, **kwargs):
+    """
+    Validate a query with StatelessValidator
+    :param query: proto Query or BlocksQuery
+    :param kwargs: StatelessValidator arguments
+    :return: list of Finding, empty if the query is valid
+    """
+    return StatelessValidator(**kwargs).validate_query(query)